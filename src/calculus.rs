@@ -0,0 +1,318 @@
+/// Richardson extrapolation of estimates whose error is a series in h²,
+/// returns the extrapolated values and the estimated error
+pub fn extrapolate(mut estimate: impl FnMut(f64) -> Vec<f64>, step: f64) -> (Vec<f64>, f64) {
+    const SHRINK: f64 = 1.4; // Ratio of successive steps
+    const TABLE_SIZE: usize = 10; // Maximum count of steps
+    const SAFE: f64 = 2.0; // Stop when error grows by this factor
+
+    let norm = |a: &[f64], b: &[f64]| {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f64::max)
+    };
+
+    let mut h = step;
+    let mut table: Vec<Vec<Vec<f64>>> = vec![vec![estimate(h)]];
+    let mut best = table[0][0].clone();
+    let mut error = f64::MAX;
+
+    for i in 1..TABLE_SIZE {
+        h /= SHRINK;
+        let mut row = vec![estimate(h)];
+        let mut factor = SHRINK * SHRINK;
+        for j in 1..=i {
+            let value: Vec<f64> = row[j - 1]
+                .iter()
+                .zip(&table[i - 1][j - 1])
+                .map(|(a, b)| (a * factor - b) / (factor - 1.0))
+                .collect();
+            factor *= SHRINK * SHRINK;
+
+            let trial = norm(&value, &row[j - 1]).max(norm(&value, &table[i - 1][j - 1]));
+            if trial <= error {
+                error = trial;
+                best = value.clone();
+            }
+            row.push(value);
+        }
+
+        // Higher order is getting worse by rounding error
+        let growth = norm(&row[i], &table[i - 1][i - 1]);
+        table.push(row);
+        if growth >= SAFE * error {
+            break;
+        }
+    }
+    (best, error)
+}
+
+/// Binomial coefficient
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Derivative of the order by central differences with Richardson extrapolation
+pub fn derivative(mut f: impl FnMut(f64) -> Vec<f64>, x: f64, order: usize) -> (Vec<f64>, f64) {
+    if order == 0 {
+        return (f(x), 0.0);
+    }
+
+    let central = |h: f64| {
+        let mut sum: Vec<f64> = Vec::new();
+        for k in 0..=order {
+            let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+            let weight = sign * binomial(order, k) / h.powi(order as i32);
+            let value = f(x + (order as f64 / 2.0 - k as f64) * h);
+            if sum.is_empty() {
+                sum = vec![0.0; value.len()];
+            }
+            for (s, v) in sum.iter_mut().zip(value) {
+                *s += weight * v;
+            }
+        }
+        sum
+    };
+    extrapolate(central, 0.1 * x.abs().max(1.0))
+}
+
+/// Mixed second partial derivative of f by the i-th and j-th variables
+pub fn mixed_partial(
+    mut f: impl FnMut(&[f64]) -> f64,
+    point: &[f64],
+    i: usize,
+    j: usize,
+) -> (f64, f64) {
+    let scale = point[i].abs().max(point[j].abs()).max(1.0);
+    let mut shifted = |di: f64, dj: f64| {
+        let mut args = point.to_vec();
+        args[i] += di;
+        args[j] += dj;
+        f(&args)
+    };
+
+    let central = |h: f64| {
+        vec![(shifted(h, h) - shifted(h, -h) - shifted(-h, h) + shifted(-h, -h)) / (4.0 * h * h)]
+    };
+    let (value, error) = extrapolate(central, 0.1 * scale);
+    (value[0], error)
+}
+
+/// Absolute and relative tolerance of integration,
+/// limited by fractions whose denominator is at most a million
+const TOLERANCE: f64 = 1e-6;
+
+/// Quadrature rule returning the integral and the estimated error
+pub type Rule = fn(&mut dyn FnMut(f64) -> f64, f64, f64) -> Option<(f64, f64)>;
+
+/// Is the error small enough for the value
+fn converged(value: f64, error: f64) -> bool {
+    error <= TOLERANCE * value.abs().max(1.0)
+}
+
+/// Composite Simpson's rule doubling intervals until it converges
+pub fn simpson(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64) -> Option<(f64, f64)> {
+    const MAX_LEVEL: usize = 20;
+
+    let ends = f(a) + f(b); // Sum of endpoint values
+    let mut evens = 0.0; // Sum of interior values of previous levels
+    let mut odds = f((a + b) / 2.0); // Sum of values added in this level
+    let mut previous = (b - a) / 6.0 * (ends + 4.0 * odds);
+
+    let mut n = 2;
+    for _ in 0..MAX_LEVEL {
+        n *= 2;
+        let h = (b - a) / n as f64;
+        evens += odds;
+        odds = (0..n / 2).map(|i| f(a + (2 * i + 1) as f64 * h)).sum();
+        let value = h / 3.0 * (ends + 4.0 * odds + 2.0 * evens);
+
+        // Richardson estimate of the error of fourth order rule
+        let error = (value - previous).abs() / 15.0;
+        if converged(value, error) {
+            return Some((value, error));
+        }
+        previous = value;
+    }
+    None
+}
+
+/// Nodes and weights of n-point Gauss-Legendre rule on [-1, 1]
+fn legendre_nodes(n: usize) -> Vec<(f64, f64)> {
+    let mut nodes = Vec::new();
+    for i in 0..n {
+        // Newton's method from Chebyshev-like initial guess
+        let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        let mut slope = 0.0;
+        for _ in 0..100 {
+            // Three-term recurrence of Legendre polynomials
+            let (mut p0, mut p1) = (1.0, x);
+            for k in 2..=n {
+                (p0, p1) = (
+                    p1,
+                    ((2 * k - 1) as f64 * x * p1 - (k - 1) as f64 * p0) / k as f64,
+                );
+            }
+            slope = n as f64 * (x * p1 - p0) / (x * x - 1.0);
+            let delta = p1 / slope;
+            x -= delta;
+            if delta.abs() < 1e-15 {
+                break;
+            }
+        }
+        nodes.push((x, 2.0 / ((1.0 - x * x) * slope * slope)));
+    }
+    nodes
+}
+
+/// Gauss-Legendre rule doubling the order until it converges
+pub fn gauss_legendre(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64) -> Option<(f64, f64)> {
+    const MAX_ORDER: usize = 256;
+
+    let (center, radius) = ((a + b) / 2.0, (b - a) / 2.0);
+    let mut rule = |n: usize| {
+        legendre_nodes(n)
+            .iter()
+            .map(|(x, w)| w * f(center + radius * x))
+            .sum::<f64>()
+            * radius
+    };
+
+    let mut n = 4;
+    let mut previous = rule(n);
+    while n < MAX_ORDER {
+        n *= 2;
+        let value = rule(n);
+        let error = (value - previous).abs();
+        if converged(value, error) {
+            return Some((value, error));
+        }
+        previous = value;
+    }
+    None
+}
+
+/// 15-point Kronrod rule with embedded 7-point Gauss rule on the interval
+fn kronrod(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64) -> (f64, f64) {
+    const NODES: [f64; 8] = [
+        0.991_455_371_120_812_6,
+        0.949_107_912_342_758_5,
+        0.864_864_423_359_769_1,
+        0.741_531_185_599_394_5,
+        0.586_087_235_467_691_1,
+        0.405_845_151_377_397_2,
+        0.207_784_955_007_898_48,
+        0.0,
+    ];
+    const KRONROD_WEIGHTS: [f64; 8] = [
+        0.022_935_322_010_529_224,
+        0.063_092_092_629_978_56,
+        0.104_790_010_322_250_19,
+        0.140_653_259_715_525_92,
+        0.169_004_726_639_267_9,
+        0.190_350_578_064_785_42,
+        0.204_432_940_075_298_89,
+        0.209_482_141_084_727_82,
+    ];
+    const GAUSS_WEIGHTS: [f64; 4] = [
+        0.129_484_966_168_869_7,
+        0.279_705_391_489_276_64,
+        0.381_830_050_505_118_9,
+        0.417_959_183_673_469_4,
+    ];
+
+    let (center, radius) = ((a + b) / 2.0, (b - a) / 2.0);
+    let mut kronrod = 0.0;
+    let mut gauss = 0.0;
+    for (i, x) in NODES.iter().enumerate() {
+        let value = if *x == 0.0 {
+            f(center)
+        } else {
+            f(center - radius * x) + f(center + radius * x)
+        };
+        kronrod += KRONROD_WEIGHTS[i] * value;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * value;
+        }
+    }
+    (kronrod * radius, ((kronrod - gauss) * radius).abs())
+}
+
+/// Adaptive Gauss-Kronrod rule bisecting the interval of largest error
+pub fn gauss_kronrod(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64) -> Option<(f64, f64)> {
+    const MAX_INTERVALS: usize = 500;
+
+    let (value, error) = kronrod(f, a, b);
+    let mut intervals = vec![(a, b, value, error)];
+
+    while intervals.len() < MAX_INTERVALS {
+        let value: f64 = intervals.iter().map(|x| x.2).sum();
+        let error: f64 = intervals.iter().map(|x| x.3).sum();
+        if converged(value, error) {
+            return Some((value, error));
+        }
+
+        let worst = (0..intervals.len())
+            .max_by(|i, j| intervals[*i].3.total_cmp(&intervals[*j].3))
+            .unwrap_or(0);
+        let (a, b, _, _) = intervals.swap_remove(worst);
+        let middle = (a + b) / 2.0;
+
+        // Interval is too narrow to bisect, the integral is likely divergent
+        if b - a <= 1e-12 * a.abs().max(b.abs()).max(1.0) {
+            return None;
+        }
+        let (left, left_error) = kronrod(f, a, middle);
+        let (right, right_error) = kronrod(f, middle, b);
+        intervals.push((a, middle, left, left_error));
+        intervals.push((middle, b, right, right_error));
+    }
+    None
+}
+
+/// Definite integral by the rule, infinite bounds are mapped onto finite interval
+pub fn integrate(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64, rule: Rule) -> Option<(f64, f64)> {
+    if a == b {
+        return Some((0.0, 0.0));
+    }
+    if a > b {
+        return integrate(f, b, a, rule).map(|(value, error)| (-value, error));
+    }
+
+    // Integrand vanishes at a singular endpoint of the substitution
+    let mut finite = |x: f64, weight: f64| {
+        if x.is_finite() && weight.is_finite() {
+            f(x) * weight
+        } else {
+            0.0
+        }
+    };
+    match (a.is_finite(), b.is_finite()) {
+        (true, true) => rule(f, a, b),
+        // x = a + t / (1 - t)
+        (true, false) => rule(
+            &mut |t| finite(a + t / (1.0 - t), 1.0 / ((1.0 - t) * (1.0 - t))),
+            0.0,
+            1.0,
+        ),
+        // x = b - (1 - t) / t
+        (false, true) => rule(&mut |t| finite(b - (1.0 - t) / t, 1.0 / (t * t)), 0.0, 1.0),
+        // x = t / (1 - t^2)
+        (false, false) => rule(
+            &mut |t| {
+                let weight = (1.0 + t * t) / ((1.0 - t * t) * (1.0 - t * t));
+                finite(t / (1.0 - t * t), weight)
+            },
+            -1.0,
+            1.0,
+        ),
+    }
+}
+
+/// Trapezoidal rule over sampled values
+pub fn trapz(x: &[f64], y: &[f64]) -> f64 {
+    x.windows(2)
+        .zip(y.windows(2))
+        .map(|(x, y)| (x[1] - x[0]) * (y[0] + y[1]) / 2.0)
+        .sum()
+}
@@ -0,0 +1,402 @@
+use crate::roots;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+
+/// Names of distributions, each has commands like normal-pdf, normal-cdf,
+/// normal-quantile and normal-sample
+pub const NAMES: [&str; 12] = [
+    "normal",
+    "uniform",
+    "exponential",
+    "gamma",
+    "beta",
+    "t",
+    "chi-squared",
+    "f",
+    "binomial",
+    "poisson",
+    "geometric",
+    "hypergeometric",
+];
+
+/// Split command like normal-cdf into the distribution and the function,
+/// which is one of pdf, pmf, cdf, quantile and sample
+pub fn parse_command(command: &str) -> Option<(&str, &str)> {
+    let (name, function) = command.rsplit_once('-')?;
+    let functions = ["pdf", "pmf", "cdf", "quantile", "sample"];
+    (NAMES.contains(&name) && functions.contains(&function)).then_some((name, function))
+}
+
+/// Logarithm of gamma function by Lanczos approximation
+pub fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection formula
+        return (PI / (PI * x).sin()).abs().ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let sum = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |acc, (i, c)| {
+            acc + c / (x + i as f64 + 1.0)
+        });
+    let t = x + G + 0.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Logarithm of beta function
+fn ln_beta(a: f64, b: f64) -> f64 {
+    ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b)
+}
+
+/// Logarithm of binomial coefficient
+fn ln_choose(n: f64, k: f64) -> f64 {
+    ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+}
+
+/// Regularized lower incomplete gamma function P(a, x)
+pub fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let prefix = (a * x.ln() - x - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // Series expansion
+        let (mut term, mut sum, mut n) = (1.0 / a, 1.0 / a, a);
+        for _ in 0..1000 {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-16 {
+                break;
+            }
+        }
+        sum * prefix
+    } else {
+        // Continued fraction of the upper function by Lentz's method
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            d = if d.abs() < tiny { tiny } else { d };
+            c = b + an / c;
+            c = if c.abs() < tiny { tiny } else { c };
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-16 {
+                break;
+            }
+        }
+        1.0 - prefix * h
+    }
+}
+
+/// Regularized incomplete beta function I_x(a, b)
+pub fn beta_i(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    // Continued fraction converges quickly on this side
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - beta_i(b, a, 1.0 - x);
+    }
+
+    let prefix = (a * x.ln() + b * (1.0 - x).ln() - ln_beta(a, b)).exp() / a;
+    let tiny = 1e-300;
+    let clamp = |x: f64| if x.abs() < tiny { tiny } else { x };
+    let mut c = 1.0;
+    let mut d = 1.0 / clamp(1.0 - (a + b) * x / (a + 1.0));
+    let mut h = d;
+    for m in 1..1000 {
+        let m = m as f64;
+        // Even step
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / clamp(1.0 + numerator * d);
+        c = clamp(1.0 + numerator / c);
+        h *= d * c;
+        // Odd step
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / clamp(1.0 + numerator * d);
+        c = clamp(1.0 + numerator / c);
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-16 {
+            break;
+        }
+    }
+    prefix * h
+}
+
+/// Error function
+pub fn erf(x: f64) -> f64 {
+    gamma_p(0.5, x * x).copysign(x)
+}
+
+/// Is the value a nonnegative integer
+fn is_count(x: f64) -> bool {
+    x >= 0.0 && x.fract() == 0.0
+}
+
+/// Probability distribution with its parameters
+#[derive(Clone, Debug)]
+pub enum Distribution {
+    Normal(f64, f64),              // Mean and standard deviation
+    Uniform(f64, f64),             // Lower and upper bounds
+    Exponential(f64),              // Rate
+    Gamma(f64, f64),               // Shape and scale
+    Beta(f64, f64),                // Shape parameters α and β
+    StudentT(f64),                 // Degrees of freedom
+    ChiSquared(f64),               // Degrees of freedom
+    F(f64, f64),                   // Degrees of freedom of numerator and denominator
+    Binomial(f64, f64),            // Count of trials and probability of success
+    Poisson(f64),                  // Mean
+    Geometric(f64),                // Probability of success, counting trials until it
+    Hypergeometric(f64, f64, f64), // Population, successes in it and draws
+}
+
+impl Distribution {
+    /// Constructor from name and parameters, fails if they are invalid
+    pub fn new(name: &str, parameters: &[f64]) -> Option<Distribution> {
+        let distribution = match (name, parameters) {
+            ("normal", [mean, sd]) if *sd > 0.0 => Distribution::Normal(*mean, *sd),
+            ("uniform", [low, high]) if low < high => Distribution::Uniform(*low, *high),
+            ("exponential", [rate]) if *rate > 0.0 => Distribution::Exponential(*rate),
+            ("gamma", [shape, scale]) if *shape > 0.0 && *scale > 0.0 => {
+                Distribution::Gamma(*shape, *scale)
+            }
+            ("beta", [a, b]) if *a > 0.0 && *b > 0.0 => Distribution::Beta(*a, *b),
+            ("t", [df]) if *df > 0.0 => Distribution::StudentT(*df),
+            ("chi-squared", [df]) if *df > 0.0 => Distribution::ChiSquared(*df),
+            ("f", [d1, d2]) if *d1 > 0.0 && *d2 > 0.0 => Distribution::F(*d1, *d2),
+            ("binomial", [n, p]) if is_count(*n) && (0.0..=1.0).contains(p) => {
+                Distribution::Binomial(*n, *p)
+            }
+            ("poisson", [mean]) if *mean > 0.0 => Distribution::Poisson(*mean),
+            ("geometric", [p]) if *p > 0.0 && *p <= 1.0 => Distribution::Geometric(*p),
+            ("hypergeometric", [population, successes, draws])
+                if is_count(*population)
+                    && is_count(*successes)
+                    && is_count(*draws)
+                    && successes <= population
+                    && draws <= population =>
+            {
+                Distribution::Hypergeometric(*population, *successes, *draws)
+            }
+            _ => return None,
+        };
+        Some(distribution)
+    }
+
+    pub fn is_discrete(&self) -> bool {
+        matches!(
+            self,
+            Distribution::Binomial(..)
+                | Distribution::Poisson(_)
+                | Distribution::Geometric(_)
+                | Distribution::Hypergeometric(..)
+        )
+    }
+
+    /// Smallest and largest values of the support
+    fn support(&self) -> (f64, f64) {
+        match self {
+            Distribution::Normal(..) | Distribution::StudentT(_) => {
+                (f64::NEG_INFINITY, f64::INFINITY)
+            }
+            Distribution::Uniform(low, high) => (*low, *high),
+            Distribution::Beta(..) => (0.0, 1.0),
+            Distribution::Binomial(n, _) => (0.0, *n),
+            Distribution::Geometric(_) => (1.0, f64::INFINITY),
+            Distribution::Hypergeometric(population, successes, draws) => (
+                (draws + successes - population).max(0.0),
+                draws.min(*successes),
+            ),
+            _ => (0.0, f64::INFINITY),
+        }
+    }
+
+    /// Probability density, or probability mass of discrete distribution
+    pub fn density(&self, x: f64) -> f64 {
+        let (low, high) = self.support();
+        if x < low || x > high || (self.is_discrete() && x.fract() != 0.0) {
+            return 0.0;
+        }
+        match self {
+            Distribution::Normal(mean, sd) => {
+                let z = (x - mean) / sd;
+                (-z * z / 2.0).exp() / (sd * (2.0 * PI).sqrt())
+            }
+            Distribution::Uniform(low, high) => 1.0 / (high - low),
+            Distribution::Exponential(rate) => rate * (-rate * x).exp(),
+            Distribution::Gamma(shape, scale) => {
+                if x == 0.0 {
+                    return match shape.partial_cmp(&1.0) {
+                        Some(Ordering::Less) => f64::INFINITY,
+                        Some(Ordering::Equal) => 1.0 / scale,
+                        _ => 0.0,
+                    };
+                }
+                ((shape - 1.0) * x.ln() - x / scale - ln_gamma(*shape) - shape * scale.ln()).exp()
+            }
+            Distribution::Beta(a, b) => {
+                x.powf(a - 1.0) * (1.0 - x).powf(b - 1.0) / ln_beta(*a, *b).exp()
+            }
+            Distribution::StudentT(df) => (ln_gamma((df + 1.0) / 2.0)
+                - ln_gamma(df / 2.0)
+                - 0.5 * (df * PI).ln()
+                - (df + 1.0) / 2.0 * (1.0 + x * x / df).ln())
+            .exp(),
+            Distribution::ChiSquared(df) => Distribution::Gamma(df / 2.0, 2.0).density(x),
+            Distribution::F(d1, d2) => {
+                if x == 0.0 {
+                    return Distribution::Beta(d1 / 2.0, d2 / 2.0).density(0.0) * d1 / d2;
+                }
+                ((d1 * (d1 * x).ln() + d2 * d2.ln() - (d1 + d2) * (d1 * x + d2).ln()) / 2.0
+                    - x.ln()
+                    - ln_beta(d1 / 2.0, d2 / 2.0))
+                .exp()
+            }
+            Distribution::Binomial(n, p) => {
+                (ln_choose(*n, x)).exp() * p.powf(x) * (1.0 - p).powf(n - x)
+            }
+            Distribution::Poisson(mean) => (x * mean.ln() - mean - ln_gamma(x + 1.0)).exp(),
+            Distribution::Geometric(p) => (1.0 - p).powf(x - 1.0) * p,
+            Distribution::Hypergeometric(population, successes, draws) => {
+                (ln_choose(*successes, x) + ln_choose(population - successes, draws - x)
+                    - ln_choose(*population, *draws))
+                .exp()
+            }
+        }
+    }
+
+    /// Cumulative distribution function
+    pub fn cdf(&self, x: f64) -> f64 {
+        let (low, high) = self.support();
+        if x < low {
+            return 0.0;
+        }
+        if x >= high {
+            return 1.0;
+        }
+        match self {
+            Distribution::Normal(mean, sd) => 0.5 * (1.0 + erf((x - mean) / (sd * 2f64.sqrt()))),
+            Distribution::Uniform(low, high) => (x - low) / (high - low),
+            Distribution::Exponential(rate) => 1.0 - (-rate * x).exp(),
+            Distribution::Gamma(shape, scale) => gamma_p(*shape, x / scale),
+            Distribution::Beta(a, b) => beta_i(*a, *b, x),
+            Distribution::StudentT(df) => {
+                let tail = 0.5 * beta_i(df / 2.0, 0.5, df / (df + x * x));
+                if x > 0.0 {
+                    1.0 - tail
+                } else {
+                    tail
+                }
+            }
+            Distribution::ChiSquared(df) => gamma_p(df / 2.0, x / 2.0),
+            Distribution::F(d1, d2) => beta_i(d1 / 2.0, d2 / 2.0, d1 * x / (d1 * x + d2)),
+            Distribution::Binomial(n, p) => {
+                let k = x.floor();
+                beta_i(n - k, k + 1.0, 1.0 - p)
+            }
+            Distribution::Poisson(mean) => 1.0 - gamma_p(x.floor() + 1.0, *mean),
+            Distribution::Geometric(p) => 1.0 - (1.0 - p).powf(x.floor()),
+            Distribution::Hypergeometric(..) => {
+                let mut k = low;
+                let mut sum = 0.0;
+                while k <= x {
+                    sum += self.density(k);
+                    k += 1.0;
+                }
+                sum.min(1.0)
+            }
+        }
+    }
+
+    /// Quantile function as inverse of the cumulative distribution function,
+    /// the smallest value whose cumulative probability reaches it for discrete one
+    pub fn quantile(&self, p: f64) -> f64 {
+        let (low, high) = self.support();
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        if p == 0.0 {
+            return low;
+        }
+        if p == 1.0 {
+            return high;
+        }
+
+        if self.is_discrete() {
+            // Exponential search for the bracket, then bisection over integers,
+            // with tolerance against rounding of the cumulative probability
+            let p = p - 1e-12;
+            let (mut below, mut above) = (low - 1.0, low);
+            let mut width = 1.0;
+            while self.cdf(above) < p {
+                if above >= high || width > 1e15 {
+                    return high;
+                }
+                below = above;
+                above = (above + width).min(high);
+                width *= 2.0;
+            }
+            while above - below > 1.0 {
+                let middle = ((below + above) / 2.0).floor();
+                if self.cdf(middle) >= p {
+                    above = middle;
+                } else {
+                    below = middle;
+                }
+            }
+            return above;
+        }
+
+        // Expand the bracket inside the support, then solve by Brent's method
+        let mut width = 1.0;
+        let mut below = if low.is_finite() { low } else { -1.0 };
+        let mut above = if high.is_finite() { high } else { 1.0 };
+        while self.cdf(below) > p && width < 1e300 {
+            below -= width;
+            width *= 2.0;
+        }
+        while self.cdf(above) < p && width < 1e300 {
+            above += width;
+            width *= 2.0;
+        }
+        roots::brent(&mut |x| self.cdf(x) - p, below, above).map_or(f64::NAN, |(x, _)| x)
+    }
+
+    /// Draw a value by inverse transform sampling
+    pub fn sample(&self, rng: &mut impl Rng) -> f64 {
+        let u = loop {
+            let u: f64 = rng.gen();
+            if u > 0.0 {
+                break u;
+            }
+        };
+        self.quantile(u)
+    }
+}
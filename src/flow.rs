@@ -0,0 +1,188 @@
+use crate::statistics::compare;
+use crate::Fraction;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+fn is_positive(x: &Fraction) -> bool {
+    compare(x, &Fraction::new(0.0)) == Ordering::Greater
+}
+
+/// Maximum flow
+pub struct Flow {
+    pub value: Fraction,
+    pub flow: Vec<Fraction>,    // Flow matrix in row-major order
+    pub source_side: Vec<bool>, // Nodes reachable from the source in the residual network
+}
+
+/// Maximum flow from the source to the sink on the square capacity matrix
+/// by Edmonds–Karp algorithm, exact for fractions
+pub fn max_flow(capacity: &[Fraction], n: usize, source: usize, sink: usize) -> Flow {
+    let zero = Fraction::new(0.0);
+    let mut residual = capacity.to_vec();
+    let mut value = zero;
+
+    // Breadth-first search for the shortest augmenting path
+    let search = |residual: &[Fraction]| {
+        let mut parent: Vec<Option<usize>> = vec![None; n];
+        let mut visited = vec![false; n];
+        visited[source] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for v in 0..n {
+                if !visited[v] && is_positive(&residual[u * n + v]) {
+                    visited[v] = true;
+                    parent[v] = Some(u);
+                    queue.push_back(v);
+                }
+            }
+        }
+        (visited, parent)
+    };
+
+    loop {
+        let (visited, parent) = search(&residual);
+        if !visited[sink] || source == sink {
+            let flow = (0..n * n)
+                .map(|k| {
+                    let excess = capacity[k] - residual[k];
+                    if is_positive(&excess) {
+                        excess
+                    } else {
+                        zero
+                    }
+                })
+                .collect();
+            return Flow {
+                value,
+                flow,
+                source_side: visited,
+            };
+        }
+
+        // Bottleneck of the path
+        let mut bottleneck: Option<Fraction> = None;
+        let mut v = sink;
+        while let Some(u) = parent[v] {
+            let r = residual[u * n + v];
+            if bottleneck.is_none_or(|b| compare(&r, &b) == Ordering::Less) {
+                bottleneck = Some(r);
+            }
+            v = u;
+        }
+        let bottleneck = bottleneck.unwrap_or(zero);
+
+        let mut v = sink;
+        while let Some(u) = parent[v] {
+            residual[u * n + v] = residual[u * n + v] - bottleneck;
+            residual[v * n + u] = residual[v * n + u] + bottleneck;
+            v = u;
+        }
+        value = value + bottleneck;
+    }
+}
+
+/// Maximum matching of bipartite graph by the biadjacency matrix of rows and columns
+/// by augmenting paths, returns the column matched to each row
+pub fn max_matching(adjacent: &[bool], (rows, cols): (usize, usize)) -> Vec<Option<usize>> {
+    fn augment(
+        row: usize,
+        adjacent: &[bool],
+        cols: usize,
+        visited: &mut [bool],
+        owner: &mut [Option<usize>],
+    ) -> bool {
+        for col in 0..cols {
+            if adjacent[row * cols + col] && !visited[col] {
+                visited[col] = true;
+                let free = match owner[col] {
+                    None => true,
+                    Some(other) => augment(other, adjacent, cols, visited, owner),
+                };
+                if free {
+                    owner[col] = Some(row);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    let mut owner: Vec<Option<usize>> = vec![None; cols];
+    for row in 0..rows {
+        let mut visited = vec![false; cols];
+        augment(row, adjacent, cols, &mut visited, &mut owner);
+    }
+    let mut matched = vec![None; rows];
+    for (col, row) in owner.iter().enumerate() {
+        if let Some(row) = row {
+            matched[*row] = Some(col);
+        }
+    }
+    matched
+}
+
+/// Assignment of rows to distinct columns minimizing total cost by Hungarian algorithm,
+/// rows must not outnumber columns
+pub fn assignment(cost: &[f64], (rows, cols): (usize, usize)) -> Option<Vec<usize>> {
+    if rows > cols {
+        return None;
+    }
+    // Potentials of rows and columns, indexed from 1 with 0 as sentinel
+    let mut u = vec![0.0; rows + 1];
+    let mut v = vec![0.0; cols + 1];
+    let mut owner = vec![0; cols + 1];
+    let mut way = vec![0; cols + 1];
+    for i in 1..=rows {
+        owner[0] = i;
+        let mut j0 = 0;
+        let mut minimum = vec![f64::INFINITY; cols + 1];
+        let mut used = vec![false; cols + 1];
+        loop {
+            used[j0] = true;
+            let i0 = owner[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=cols {
+                if !used[j] {
+                    let reduced = cost[(i0 - 1) * cols + (j - 1)] - u[i0] - v[j];
+                    if reduced < minimum[j] {
+                        minimum[j] = reduced;
+                        way[j] = j0;
+                    }
+                    if minimum[j] < delta {
+                        delta = minimum[j];
+                        j1 = j;
+                    }
+                }
+            }
+            if !delta.is_finite() {
+                return None;
+            }
+            for j in 0..=cols {
+                if used[j] {
+                    u[owner[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minimum[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if owner[j0] == 0 {
+                break;
+            }
+        }
+        // Flip the alternating path
+        while j0 != 0 {
+            let j1 = way[j0];
+            owner[j0] = owner[j1];
+            j0 = j1;
+        }
+    }
+    let mut assigned = vec![0; rows];
+    for j in 1..=cols {
+        if owner[j] != 0 {
+            assigned[owner[j] - 1] = j - 1;
+        }
+    }
+    Some(assigned)
+}
@@ -0,0 +1,160 @@
+use crate::Fraction;
+use nalgebra::Complex;
+use std::f64::consts::PI;
+
+/// Complex number on the unit circle
+fn unit(angle: f64) -> Complex<f64> {
+    Complex::new(angle.cos(), angle.sin())
+}
+
+/// Discrete Fourier transform, inverse one if the sign is positive and unnormalized
+fn transform(x: &[Complex<f64>], sign: f64) -> Vec<Complex<f64>> {
+    let n = x.len();
+    if n <= 1 {
+        x.to_vec()
+    } else if n.is_power_of_two() {
+        radix2(x, sign)
+    } else {
+        bluestein(x, sign)
+    }
+}
+
+/// Iterative Cooley-Tukey algorithm for length of power of two
+fn radix2(x: &[Complex<f64>], sign: f64) -> Vec<Complex<f64>> {
+    let n = x.len();
+    let bits = n.trailing_zeros();
+
+    // Bit reversal permutation
+    let mut result: Vec<Complex<f64>> = (0..n)
+        .map(|i| x[i.reverse_bits() >> (usize::BITS - bits)])
+        .collect();
+
+    let mut length = 2;
+    while length <= n {
+        let angle = sign * 2.0 * PI / length as f64;
+        for start in (0..n).step_by(length) {
+            for k in 0..length / 2 {
+                let twiddle = unit(angle * k as f64);
+                let even = result[start + k];
+                let odd = result[start + k + length / 2] * twiddle;
+                result[start + k] = even + odd;
+                result[start + k + length / 2] = even - odd;
+            }
+        }
+        length *= 2;
+    }
+    result
+}
+
+/// Bluestein's algorithm expressing transform of arbitrary length as convolution
+fn bluestein(x: &[Complex<f64>], sign: f64) -> Vec<Complex<f64>> {
+    let n = x.len();
+    let m = (2 * n - 1).next_power_of_two();
+
+    // Chirp exp(-sign * πi k² / n), where k² is reduced modulo 2n against loss of precision
+    let chirp: Vec<Complex<f64>> = (0..n)
+        .map(|k| unit(-sign * PI * ((k * k) % (2 * n)) as f64 / n as f64))
+        .collect();
+
+    let mut a = vec![Complex::new(0.0, 0.0); m];
+    for k in 0..n {
+        a[k] = x[k] * chirp[k].conj();
+    }
+    let mut b = vec![Complex::new(0.0, 0.0); m];
+    b[0] = chirp[0];
+    for k in 1..n {
+        b[k] = chirp[k];
+        b[m - k] = chirp[k];
+    }
+
+    let (a, b) = (radix2(&a, -1.0), radix2(&b, -1.0));
+    let product: Vec<Complex<f64>> = a.iter().zip(&b).map(|(a, b)| a * b).collect();
+    let convolution = radix2(&product, 1.0);
+    (0..n)
+        .map(|k| convolution[k] / m as f64 * chirp[k].conj())
+        .collect()
+}
+
+/// Fast Fourier transform
+pub fn fft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    transform(x, -1.0)
+}
+
+/// Inverse fast Fourier transform normalized by the length
+pub fn ifft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let n = x.len() as f64;
+    transform(x, 1.0).into_iter().map(|z| z / n).collect()
+}
+
+/// Transform of real signal up to the Nyquist frequency, the rest is conjugate symmetric
+pub fn rfft(x: &[f64]) -> Vec<Complex<f64>> {
+    let complex: Vec<Complex<f64>> = x.iter().map(|x| Complex::new(*x, 0.0)).collect();
+    let mut result = fft(&complex);
+    result.truncate(x.len() / 2 + 1);
+    result
+}
+
+/// Linear convolution by the definition, exact for fractions
+pub fn convolve_direct(a: &[Fraction], b: &[Fraction]) -> Vec<Fraction> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut result = vec![Fraction::new(0.0); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] = result[i + j] + *x * *y;
+        }
+    }
+    result
+}
+
+/// Linear convolution by multiplying transforms of zero padded signals
+pub fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let length = a.len() + b.len() - 1;
+    let padded = |x: &[f64]| {
+        let mut padded = vec![Complex::new(0.0, 0.0); length.next_power_of_two()];
+        for (p, x) in padded.iter_mut().zip(x) {
+            *p = Complex::new(*x, 0.0);
+        }
+        fft(&padded)
+    };
+    let product: Vec<Complex<f64>> = padded(a)
+        .iter()
+        .zip(&padded(b))
+        .map(|(a, b)| a * b)
+        .collect();
+    let mut result: Vec<f64> = ifft(&product).iter().map(|z| z.re).collect();
+    result.truncate(length);
+    result
+}
+
+/// Window function of the length, symmetric to be used for filter design
+pub fn window(kind: &str, n: usize) -> Option<Vec<f64>> {
+    // Coefficients of cosine terms
+    let coefficients: &[f64] = match kind {
+        "hann" => &[0.5, 0.5],
+        "hamming" => &[0.54, 0.46],
+        "blackman" => &[0.42, 0.5, 0.08],
+        _ => return None,
+    };
+    if n == 1 {
+        return Some(vec![1.0]);
+    }
+    Some(
+        (0..n)
+            .map(|k| {
+                let phase = 2.0 * PI * k as f64 / (n - 1) as f64;
+                coefficients
+                    .iter()
+                    .enumerate()
+                    .map(
+                        |(j, c)| if j % 2 == 0 { 1.0 } else { -1.0 } * c * (j as f64 * phase).cos(),
+                    )
+                    .sum()
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,797 @@
+use crate::Fraction;
+use petgraph::algo;
+use petgraph::data::FromElements;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::unionfind::UnionFind;
+use petgraph::visit::{Bfs, Dfs};
+use petgraph::{EdgeType, Graph, Undirected};
+use rand::Rng;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Exact number of the distance, infinity is 1/0
+pub fn distance(x: f64) -> Fraction {
+    if x.is_infinite() {
+        Fraction {
+            numerator: x.signum() as isize,
+            denominator: 0,
+        }
+    } else {
+        Fraction::new(x)
+    }
+}
+
+/// Order of nodes visited from the start by breadth-first or depth-first search
+fn traverse<Ty: EdgeType>(
+    graph: &Graph<String, f64, Ty>,
+    start: usize,
+    breadth: bool,
+) -> Vec<usize> {
+    let start = NodeIndex::new(start);
+    let mut order = Vec::new();
+    if breadth {
+        let mut bfs = Bfs::new(graph, start);
+        while let Some(node) = bfs.next(graph) {
+            order.push(node.index());
+        }
+    } else {
+        let mut dfs = Dfs::new(graph, start);
+        while let Some(node) = dfs.next(graph) {
+            order.push(node.index());
+        }
+    }
+    order
+}
+
+/// Distances from the source by Dijkstra's algorithm, unreachable nodes are infinity
+fn dijkstra<Ty: EdgeType>(graph: &Graph<String, f64, Ty>, source: usize) -> Vec<f64> {
+    let scores = algo::dijkstra(graph, NodeIndex::new(source), None, |e| *e.weight());
+    (0..graph.node_count())
+        .map(|i| *scores.get(&NodeIndex::new(i)).unwrap_or(&f64::INFINITY))
+        .collect()
+}
+
+/// Distances from the source by Bellman-Ford algorithm, fails if a negative cycle is reachable
+fn bellman_ford<Ty: EdgeType>(graph: &Graph<String, f64, Ty>, source: usize) -> Option<Vec<f64>> {
+    algo::bellman_ford(graph, NodeIndex::new(source))
+        .ok()
+        .map(|(distances, _)| distances)
+}
+
+/// Sort the groups of nodes by their smallest ones
+fn sorted_groups(mut groups: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    for group in groups.iter_mut() {
+        group.sort_unstable();
+    }
+    groups.sort();
+    groups
+}
+
+/// Graph of labeled nodes and weighted edges, which is directed or undirected
+#[derive(Clone, Debug)]
+pub struct Network {
+    graph: Graph<String, f64>,
+    directed: bool,
+}
+
+/// Split line of edge list by whitespace, where quoted fields may contain it
+/// and doubled quotes, fails if quotation is not closed
+fn split_fields(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut field = String::new();
+        if c == '"' {
+            loop {
+                match chars.next()? {
+                    '"' if chars.peek() == Some(&'"') => {
+                        field.push('"');
+                        chars.next();
+                    }
+                    '"' => break,
+                    c => field.push(c),
+                }
+            }
+        } else {
+            field.push(c);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                field.push(c);
+            }
+        }
+        fields.push(field);
+    }
+    Some(fields)
+}
+
+impl Network {
+    pub fn new(directed: bool) -> Network {
+        Network {
+            graph: Graph::new(),
+            directed,
+        }
+    }
+
+    /// Graph whose edges are nonzero elements of the square matrix, labeled by indices,
+    /// undirected one takes the upper triangle or the lower one if it is zero
+    pub fn from_matrix(data: &[Fraction], size: usize, directed: bool) -> Network {
+        let mut network = Network::new(directed);
+        for i in 0..size {
+            network.add_node(i.to_string());
+        }
+        for i in 0..size {
+            for j in 0..size {
+                if !directed && i > j {
+                    continue;
+                }
+                let mut weight = data[i * size + j];
+                if !directed && weight.numerator == 0 {
+                    weight = data[j * size + i];
+                }
+                if weight.numerator != 0 {
+                    network.add_edge(i, j, weight.to_f64());
+                }
+            }
+        }
+        network
+    }
+
+    pub fn len(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    pub fn add_node(&mut self, label: String) -> usize {
+        self.graph.add_node(label).index()
+    }
+
+    pub fn labels(&self) -> Vec<String> {
+        self.graph
+            .node_indices()
+            .map(|n| self.graph[n].clone())
+            .collect()
+    }
+
+    /// Relabel the nodes in order, fails if the count is different
+    pub fn set_labels(&mut self, labels: Vec<String>) -> Option<()> {
+        if labels.len() != self.len() {
+            return None;
+        }
+        for (weight, label) in self.graph.node_weights_mut().zip(labels) {
+            *weight = label;
+        }
+        Some(())
+    }
+
+    /// Add edge between the existing nodes
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: f64) -> Option<()> {
+        if from >= self.len() || to >= self.len() {
+            return None;
+        }
+        self.graph
+            .add_edge(NodeIndex::new(from), NodeIndex::new(to), weight);
+        Some(())
+    }
+
+    /// Edge from the node to the other, either direction if it is undirected
+    fn find_edge(&self, from: usize, to: usize) -> Option<EdgeIndex> {
+        if from >= self.len() || to >= self.len() {
+            return None;
+        }
+        let (a, b) = (NodeIndex::new(from), NodeIndex::new(to));
+        if self.directed {
+            self.graph.find_edge(a, b)
+        } else {
+            self.graph.find_edge_undirected(a, b).map(|(edge, _)| edge)
+        }
+    }
+
+    /// Remove edge between the nodes, returns its weight
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Option<f64> {
+        let edge = self.find_edge(from, to)?;
+        self.graph.remove_edge(edge)
+    }
+
+    /// Nodes adjacent to the node, successors if it is directed, in ascending order
+    pub fn neighbors(&self, node: usize) -> Option<Vec<usize>> {
+        if node >= self.len() {
+            return None;
+        }
+        let node = NodeIndex::new(node);
+        let mut neighbors: Vec<usize> = if self.directed {
+            self.graph.neighbors(node).map(|n| n.index()).collect()
+        } else {
+            self.graph
+                .neighbors_undirected(node)
+                .map(|n| n.index())
+                .collect()
+        };
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        Some(neighbors)
+    }
+
+    /// Count of edge ends at the node, sum of in-degree and out-degree if it is directed
+    pub fn degree(&self, node: usize) -> Option<usize> {
+        if node >= self.len() {
+            return None;
+        }
+        let node = NodeIndex::new(node);
+        Some(
+            self.graph
+                .edge_indices()
+                .filter_map(|e| self.graph.edge_endpoints(e))
+                .map(|(a, b)| (a == node) as usize + (b == node) as usize)
+                .sum(),
+        )
+    }
+
+    /// Edges as (from, to, weight)
+    pub fn edges(&self) -> Vec<(usize, usize, f64)> {
+        self.graph
+            .edge_indices()
+            .filter_map(|e| {
+                let (a, b) = self.graph.edge_endpoints(e)?;
+                Some((a.index(), b.index(), self.graph[e]))
+            })
+            .collect()
+    }
+
+    /// Adjacency matrix of weights in row-major order, parallel edges are summed
+    pub fn adjacency_matrix(&self) -> Vec<f64> {
+        let n = self.len();
+        let mut matrix = vec![0.0; n * n];
+        for (a, b, weight) in self.edges() {
+            matrix[a * n + b] += weight;
+            if !self.directed && a != b {
+                matrix[b * n + a] += weight;
+            }
+        }
+        matrix
+    }
+
+    /// Undirected view of the graph
+    pub fn undirected(&self) -> Graph<String, f64, Undirected> {
+        self.graph.clone().into_edge_type()
+    }
+
+    /// DOT language of the graph, whose nodes and edges are labeled by labels and weights
+    pub fn to_dot(&self) -> String {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut text = format!("{kind} {{\n");
+        for (i, label) in self.labels().iter().enumerate() {
+            text += &format!("    {i} [ label = \"{}\" ]\n", label.replace('"', "\\\""));
+        }
+        for (a, b, weight) in self.edges() {
+            text += &format!("    {a} {arrow} {b} [ label = \"{weight}\" ]\n");
+        }
+        text + "}\n"
+    }
+
+    /// Edge list of lines like "from to weight" by labels of nodes, which are quoted
+    /// like "New York" if they are empty or contain whitespace, quotes or #
+    pub fn to_edge_list(&self) -> String {
+        let labels: Vec<String> = self
+            .labels()
+            .iter()
+            .map(|label| {
+                if label.is_empty()
+                    || label.contains(|c: char| c.is_whitespace() || c == '"' || c == '#')
+                {
+                    format!("\"{}\"", label.replace('"', "\"\""))
+                } else {
+                    label.clone()
+                }
+            })
+            .collect();
+        self.edges()
+            .iter()
+            .map(|(a, b, weight)| format!("{} {} {weight}\n", labels[*a], labels[*b]))
+            .collect()
+    }
+
+    /// Parse edge list of lines like "from to weight" by labels of nodes, which may be quoted,
+    /// whose weight is 1 if omitted, nodes are made in order of appearance,
+    /// and lines of # are comments
+    pub fn from_edge_list(text: &str, directed: bool) -> Result<Network, String> {
+        let mut network = Network::new(directed);
+        let mut nodes: HashMap<String, usize> = HashMap::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = split_fields(line)
+                .ok_or(format!("quotation of line {} is not closed", number + 1))?;
+            let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+            let (from, to, weight) = match fields[..] {
+                [from, to] => (from, to, 1.0),
+                [from, to, weight] => match weight.parse::<f64>() {
+                    Ok(weight) => (from, to, weight),
+                    Err(_) => return Err(format!("weight of line {} is invalid", number + 1)),
+                },
+                _ => return Err(format!("line {} is not an edge", number + 1)),
+            };
+            let mut node = |label: &str| {
+                *nodes
+                    .entry(label.to_string())
+                    .or_insert_with(|| network.graph.add_node(label.to_string()).index())
+            };
+            let (from, to) = (node(from), node(to));
+            network.add_edge(from, to, weight);
+        }
+        Ok(network)
+    }
+
+    /// GraphML whose nodes have data of label and edges have data of weight
+    pub fn to_graphml(&self) -> String {
+        let escape = |text: &str| {
+            text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+        };
+        let mut text = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n",
+        ));
+        let kind = if self.directed {
+            "directed"
+        } else {
+            "undirected"
+        };
+        text += &format!("  <graph edgedefault=\"{kind}\">\n");
+        for (i, label) in self.labels().iter().enumerate() {
+            text += &format!(
+                "    <node id=\"n{i}\"><data key=\"label\">{}</data></node>\n",
+                escape(label)
+            );
+        }
+        for (a, b, weight) in self.edges() {
+            text += &format!(
+                "    <edge source=\"n{a}\" target=\"n{b}\"><data key=\"weight\">{weight}</data></edge>\n"
+            );
+        }
+        text + "  </graph>\n</graphml>\n"
+    }
+
+    /// Parse GraphML, nodes are labeled by data of label or their ids,
+    /// and edges are weighted by data of weight or 1
+    pub fn from_graphml(text: &str) -> Result<Network, String> {
+        let unescape = |text: &str| {
+            text.replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&")
+        };
+        let regex = |pattern: &str| Regex::new(pattern).map_err(|e| e.to_string());
+        let graph = regex(r#"<graph\b[^>]*>"#)?;
+        let directed = regex(r#"edgedefault\s*=\s*"directed""#)?;
+        let node = regex(r#"(?s)<node\b[^>]*\bid\s*=\s*"([^"]*)"[^>]*?(?:/>|>(.*?)</node>)"#)?;
+        let edge = regex(r#"(?s)<edge\b([^>]*?)(?:/>|>(.*?)</edge>)"#)?;
+        let attribute = |name: &str| regex(&format!(r#"\b{name}\s*=\s*"([^"]*)""#));
+        let (source, target) = (attribute("source")?, attribute("target")?);
+        let data = |key: &str| {
+            regex(&format!(
+                r#"(?s)<data\b[^>]*\bkey\s*=\s*"{}"[^>]*>(.*?)</data>"#,
+                regex::escape(key)
+            ))
+        };
+
+        // Ids of keys declared for the attribute names like <key id="d0" attr.name="weight">,
+        // which are the names themselves if undeclared
+        let key = regex(r#"<key\b[^>]*>"#)?;
+        let (key_id, key_name) = (attribute("id")?, attribute(r"attr\.name")?);
+        let key_domain = attribute("for")?;
+        let declared = |name: &str, element: &str| {
+            key.find_iter(text)
+                .map(|m| m.as_str())
+                .find(|k| {
+                    let value = |regex: &Regex| regex.captures(k).map(|c| c[1].to_string());
+                    value(&key_name).as_deref() == Some(name)
+                        && value(&key_domain).is_none_or(|d| d == element || d == "all")
+                })
+                .and_then(|k| key_id.captures(k).map(|c| unescape(&c[1])))
+                .unwrap_or(name.to_string())
+        };
+        let (label, weight) = (
+            data(&declared("label", "node"))?,
+            data(&declared("weight", "edge"))?,
+        );
+
+        let header = graph.find(text).ok_or("graph element is missing")?;
+        let mut network = Network::new(directed.is_match(header.as_str()));
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        for captures in node.captures_iter(text) {
+            let id = unescape(&captures[1]);
+            let body = captures.get(2).map_or("", |m| m.as_str());
+            let name = label
+                .captures(body)
+                .map_or(id.clone(), |c| unescape(c[1].trim()));
+            ids.insert(id, network.add_node(name));
+        }
+        for captures in edge.captures_iter(text) {
+            let endpoint = |regex: &Regex| -> Result<usize, String> {
+                let id = regex
+                    .captures(&captures[1])
+                    .map(|c| unescape(&c[1]))
+                    .ok_or("endpoint of edge is missing")?;
+                ids.get(&id)
+                    .copied()
+                    .ok_or(format!("node {id} is not found"))
+            };
+            let (from, to) = (endpoint(&source)?, endpoint(&target)?);
+            let body = captures.get(2).map_or("", |m| m.as_str());
+            let value = match weight.captures(body) {
+                Some(c) => c[1]
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| "weight of edge is invalid".to_string())?,
+                None => 1.0,
+            };
+            network.add_edge(from, to, value);
+        }
+        Ok(network)
+    }
+
+    /// Undirected graph of the count of nodes whose unit edges join the pairs
+    fn generate(count: usize, pairs: impl Iterator<Item = (usize, usize)>) -> Network {
+        let mut network = Network::new(false);
+        for i in 0..count {
+            network.add_node(i.to_string());
+        }
+        for (a, b) in pairs {
+            network.add_edge(a, b, 1.0);
+        }
+        network
+    }
+
+    /// Complete graph
+    pub fn complete(n: usize) -> Network {
+        Network::generate(n, (0..n).flat_map(|a| (a + 1..n).map(move |b| (a, b))))
+    }
+
+    /// Cycle graph
+    pub fn cycle(n: usize) -> Network {
+        match n {
+            0..=2 => Network::path(n),
+            _ => Network::generate(n, (0..n).map(|a| (a, (a + 1) % n))),
+        }
+    }
+
+    /// Path graph
+    pub fn path(n: usize) -> Network {
+        Network::generate(n, (1..n).map(|b| (b - 1, b)))
+    }
+
+    /// Grid graph of the rows and columns, nodes are numbered in row-major order
+    pub fn grid(rows: usize, cols: usize) -> Network {
+        let right = (0..rows * cols)
+            .filter(|k| k % cols + 1 < cols)
+            .map(|k| (k, k + 1));
+        let down = (0..rows.saturating_sub(1) * cols).map(|k| (k, k + cols));
+        Network::generate(rows * cols, right.chain(down))
+    }
+
+    /// Star graph whose center is the node 0
+    pub fn star(n: usize) -> Network {
+        Network::generate(n, (1..n).map(|b| (0, b)))
+    }
+
+    /// Erdős–Rényi random graph whose each pair is joined with the probability
+    pub fn random(n: usize, p: f64, rng: &mut impl Rng) -> Network {
+        let pairs: Vec<(usize, usize)> = (0..n)
+            .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+            .filter(|_| rng.gen::<f64>() < p)
+            .collect();
+        Network::generate(n, pairs.into_iter())
+    }
+
+    /// Order of nodes visited by breadth-first or depth-first search from the start
+    pub fn traverse(&self, start: usize, breadth: bool) -> Option<Vec<usize>> {
+        if start >= self.len() {
+            return None;
+        }
+        Some(if self.directed {
+            traverse(&self.graph, start, breadth)
+        } else {
+            traverse(&self.undirected(), start, breadth)
+        })
+    }
+
+    /// Shortest distances from the source by Dijkstra's algorithm,
+    /// fails if the node is not in the graph or a weight is negative
+    pub fn dijkstra(&self, source: usize) -> Option<Vec<f64>> {
+        if source >= self.len() || self.edges().iter().any(|(_, _, w)| *w < 0.0) {
+            return None;
+        }
+        Some(if self.directed {
+            dijkstra(&self.graph, source)
+        } else {
+            dijkstra(&self.undirected(), source)
+        })
+    }
+
+    /// Shortest distances from the source by Bellman-Ford algorithm,
+    /// fails if a negative cycle is reachable
+    pub fn bellman_ford(&self, source: usize) -> Option<Vec<f64>> {
+        if self.directed {
+            bellman_ford(&self.graph, source)
+        } else {
+            bellman_ford(&self.undirected(), source)
+        }
+    }
+
+    /// Matrix of shortest distances between all pairs by Floyd-Warshall algorithm,
+    /// fails if there is a negative cycle
+    pub fn floyd_warshall(&self) -> Option<Vec<f64>> {
+        let n = self.len();
+        let mut distances = vec![f64::INFINITY; n * n];
+        for i in 0..n {
+            distances[i * n + i] = 0.0;
+        }
+        for (a, b, weight) in self.edges() {
+            distances[a * n + b] = distances[a * n + b].min(weight);
+            if !self.directed {
+                distances[b * n + a] = distances[b * n + a].min(weight);
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    let through = distances[i * n + k] + distances[k * n + j];
+                    if through < distances[i * n + j] {
+                        distances[i * n + j] = through;
+                    }
+                }
+            }
+        }
+        (0..n)
+            .all(|i| distances[i * n + i] >= 0.0)
+            .then_some(distances)
+    }
+
+    /// Topological order of directed acyclic graph
+    pub fn toposort(&self) -> Option<Vec<usize>> {
+        if !self.directed {
+            return None;
+        }
+        let order = algo::toposort(&self.graph, None).ok()?;
+        Some(order.into_iter().map(|n| n.index()).collect())
+    }
+
+    /// Groups of connected nodes, weakly connected ones if it is directed
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut union_find = UnionFind::new(self.len());
+        for (a, b, _) in self.edges() {
+            union_find.union(a, b);
+        }
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut roots: Vec<usize> = Vec::new();
+        for node in 0..self.len() {
+            let root = union_find.find(node);
+            match roots.iter().position(|r| *r == root) {
+                Some(i) => groups[i].push(node),
+                None => {
+                    roots.push(root);
+                    groups.push(vec![node]);
+                }
+            }
+        }
+        sorted_groups(groups)
+    }
+
+    /// Groups of strongly connected nodes by Tarjan's algorithm
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        if !self.directed {
+            return self.connected_components();
+        }
+        let groups = algo::tarjan_scc(&self.graph)
+            .into_iter()
+            .map(|group| group.into_iter().map(|n| n.index()).collect())
+            .collect();
+        sorted_groups(groups)
+    }
+
+    /// Minimum spanning forest by Kruskal's algorithm, regarding the graph as undirected
+    pub fn min_spanning_tree(&self) -> Network {
+        let undirected = self.undirected();
+        let tree: Graph<String, f64, Undirected> =
+            Graph::from_elements(algo::min_spanning_tree(&undirected));
+        Network {
+            graph: tree.into_edge_type(),
+            directed: false,
+        }
+    }
+
+    /// Is there a cycle, an edge to itself is a cycle
+    pub fn has_cycle(&self) -> bool {
+        if self.directed {
+            algo::is_cyclic_directed(&self.graph)
+        } else {
+            algo::is_cyclic_undirected(&self.graph) || self.edges().iter().any(|(a, b, _)| a == b)
+        }
+    }
+
+    /// Successors of each node, neighbors if it is undirected
+    fn adjacency_lists(&self) -> Vec<Vec<usize>> {
+        (0..self.len())
+            .map(|node| self.neighbors(node).unwrap_or_default())
+            .collect()
+    }
+
+    /// Counts of edges of shortest paths from the source, None for unreachable nodes
+    fn hops(&self, adjacency: &[Vec<usize>], source: usize) -> Vec<Option<usize>> {
+        let mut hops = vec![None; self.len()];
+        hops[source] = Some(0);
+        let mut queue = std::collections::VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for v in &adjacency[u] {
+                if hops[*v].is_none() {
+                    hops[*v] = hops[u].map(|h| h + 1);
+                    queue.push_back(*v);
+                }
+            }
+        }
+        hops
+    }
+
+    /// PageRank of the damping factor, weighted by edges, nodes without out-edges
+    /// distribute evenly, fails if it does not converge
+    pub fn pagerank(&self, damping: f64) -> Option<Vec<f64>> {
+        let n = self.len();
+        let matrix = self.adjacency_matrix();
+        let out: Vec<f64> = (0..n)
+            .map(|i| matrix[i * n..(i + 1) * n].iter().sum())
+            .collect();
+        let mut rank = vec![1.0 / n as f64; n];
+        for _ in 0..1000 {
+            let dangling: f64 = (0..n).filter(|i| out[*i] == 0.0).map(|i| rank[i]).sum();
+            let next: Vec<f64> = (0..n)
+                .map(|j| {
+                    let inflow: f64 = (0..n)
+                        .filter(|i| out[*i] != 0.0)
+                        .map(|i| rank[i] * matrix[i * n + j] / out[i])
+                        .sum();
+                    (1.0 - damping) / n as f64 + damping * (inflow + dangling / n as f64)
+                })
+                .collect();
+            let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+            rank = next;
+            if change < 1e-12 {
+                return Some(rank);
+            }
+        }
+        None
+    }
+
+    /// Betweenness centrality as count of shortest paths between other nodes
+    /// passing through each node by Brandes' algorithm, ignoring weights
+    pub fn betweenness(&self) -> Vec<f64> {
+        let n = self.len();
+        let adjacency = self.adjacency_lists();
+        let mut centrality = vec![0.0; n];
+        for source in 0..n {
+            // Counts of shortest paths and predecessors on them
+            let mut order = Vec::new();
+            let mut predecessors: Vec<Vec<usize>> = vec![vec![]; n];
+            let mut paths = vec![0.0; n];
+            let mut hops: Vec<Option<usize>> = vec![None; n];
+            paths[source] = 1.0;
+            hops[source] = Some(0);
+            let mut queue = std::collections::VecDeque::from([source]);
+            while let Some(u) = queue.pop_front() {
+                order.push(u);
+                for &v in &adjacency[u] {
+                    if hops[v].is_none() {
+                        hops[v] = hops[u].map(|h| h + 1);
+                        queue.push_back(v);
+                    }
+                    if hops[v] == hops[u].map(|h| h + 1) {
+                        paths[v] += paths[u];
+                        predecessors[v].push(u);
+                    }
+                }
+            }
+
+            // Accumulate dependencies in order of decreasing distance
+            let mut dependency = vec![0.0; n];
+            for &w in order.iter().rev() {
+                for &v in &predecessors[w] {
+                    dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+                }
+                if w != source {
+                    centrality[w] += dependency[w];
+                }
+            }
+        }
+        if !self.directed {
+            // Each path is counted from both ends
+            for c in centrality.iter_mut() {
+                *c /= 2.0;
+            }
+        }
+        centrality
+    }
+
+    /// Closeness centrality by distances in edges to reachable nodes,
+    /// scaled by the fraction of nodes reached
+    pub fn closeness(&self) -> Vec<f64> {
+        let n = self.len();
+        let adjacency = self.adjacency_lists();
+        (0..n)
+            .map(|source| {
+                let hops: Vec<usize> = self
+                    .hops(&adjacency, source)
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                let total: usize = hops.iter().sum();
+                let reached = (hops.len() - 1) as f64;
+                if total == 0 {
+                    0.0
+                } else {
+                    reached / total as f64 * reached / (n - 1) as f64
+                }
+            })
+            .collect()
+    }
+
+    /// Eigenvector centrality by power iteration of weights of in-edges,
+    /// normalized to unit length, fails if it does not converge
+    pub fn eigenvector_centrality(&self) -> Option<Vec<f64>> {
+        let n = self.len();
+        let matrix = self.adjacency_matrix();
+        let mut x = vec![1.0 / n as f64; n];
+        for _ in 0..1000 {
+            // Shifted by identity against oscillation of bipartite graphs
+            let mut next: Vec<f64> = (0..n)
+                .map(|j| x[j] + (0..n).map(|i| matrix[i * n + j] * x[i]).sum::<f64>())
+                .collect();
+            let norm = next.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                return None;
+            }
+            for v in next.iter_mut() {
+                *v /= norm;
+            }
+            let change: f64 = next.iter().zip(&x).map(|(a, b)| (a - b).abs()).sum();
+            x = next;
+            if change < n as f64 * 1e-12 {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    pub fn display(&self) -> String {
+        let labels: Vec<String> = self
+            .graph
+            .node_indices()
+            .map(|n| format!("({})", self.graph[n]))
+            .collect();
+        let edges: Vec<String> = self
+            .edges()
+            .iter()
+            .map(|(a, b, weight)| format!("[{a} {b} {}]", Fraction::new(*weight).display()))
+            .collect();
+        format!(
+            "graph:[({}) [{}] [{}]]",
+            if self.directed {
+                "directed"
+            } else {
+                "undirected"
+            },
+            labels.join(" "),
+            edges.join(" ")
+        )
+    }
+}
@@ -0,0 +1,313 @@
+use crate::distribution::Distribution;
+use std::cmp::Ordering;
+
+/// Result of hypothesis test, whose p-value is two-sided except for
+/// the tests by chi-squared and F distributions
+pub struct Test {
+    pub statistic: f64,
+    pub p_value: f64,
+    pub degrees: Vec<f64>, // Degrees of freedom, empty if the distribution has none
+}
+
+fn mean(x: &[f64]) -> f64 {
+    x.iter().sum::<f64>() / x.len() as f64
+}
+
+/// Unbiased variance of sample
+fn variance(x: &[f64]) -> f64 {
+    let mean = mean(x);
+    x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (x.len() - 1) as f64
+}
+
+/// Upper tail probability of the distribution
+fn upper_tail(name: &str, parameters: &[f64], x: f64) -> f64 {
+    Distribution::new(name, parameters).map_or(f64::NAN, |d| 1.0 - d.cdf(x))
+}
+
+/// Two-sided p-value of statistic by symmetric distribution
+fn two_sided(name: &str, parameters: &[f64], x: f64) -> f64 {
+    (2.0 * upper_tail(name, parameters, x.abs())).min(1.0)
+}
+
+/// Test by t distribution of the statistic
+fn t_test(t: f64, degrees: f64) -> Option<Test> {
+    t.is_finite().then(|| Test {
+        statistic: t,
+        p_value: two_sided("t", &[degrees], t),
+        degrees: vec![degrees],
+    })
+}
+
+/// Ranks from 1 of the values, average ones among ties
+fn ranks(x: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..x.len()).collect();
+    order.sort_by(|a, b| x[*a].partial_cmp(&x[*b]).unwrap_or(Ordering::Equal));
+    let mut ranks = vec![0.0; x.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let ties = order[i..]
+            .iter()
+            .take_while(|j| x[**j] == x[order[i]])
+            .count();
+        let rank = i as f64 + (ties as f64 + 1.0) / 2.0;
+        for j in &order[i..i + ties] {
+            ranks[*j] = rank;
+        }
+        i += ties;
+    }
+    ranks
+}
+
+/// One-sample t-test whether the mean is the value
+pub fn t_one(x: &[f64], mu: f64) -> Option<Test> {
+    if x.len() < 2 {
+        return None;
+    }
+    let n = x.len() as f64;
+    t_test((mean(x) - mu) / (variance(x) / n).sqrt(), n - 1.0)
+}
+
+/// Paired t-test whether the mean of differences is zero
+pub fn t_paired(x: &[f64], y: &[f64]) -> Option<Test> {
+    if x.len() != y.len() {
+        return None;
+    }
+    let differences: Vec<f64> = x.iter().zip(y).map(|(x, y)| x - y).collect();
+    t_one(&differences, 0.0)
+}
+
+/// Welch's t-test whether the means are equal without assuming equal variances
+pub fn t_welch(x: &[f64], y: &[f64]) -> Option<Test> {
+    if x.len() < 2 || y.len() < 2 {
+        return None;
+    }
+    let a = variance(x) / x.len() as f64;
+    let b = variance(y) / y.len() as f64;
+    // Welch–Satterthwaite equation
+    let degrees = (a + b).powi(2) / (a * a / (x.len() - 1) as f64 + b * b / (y.len() - 1) as f64);
+    t_test((mean(x) - mean(y)) / (a + b).sqrt(), degrees)
+}
+
+/// Z-test whether the mean is the value, for the known standard deviation
+pub fn z_test(x: &[f64], mu: f64, sigma: f64) -> Option<Test> {
+    if x.is_empty() || sigma <= 0.0 {
+        return None;
+    }
+    let z = (mean(x) - mu) / (sigma / (x.len() as f64).sqrt());
+    Some(Test {
+        statistic: z,
+        p_value: two_sided("normal", &[0.0, 1.0], z),
+        degrees: vec![],
+    })
+}
+
+/// Test by chi-squared statistic of the observed and expected counts
+fn chi_squared(observed: &[f64], expected: &[f64], degrees: f64) -> Option<Test> {
+    if expected.iter().any(|e| *e <= 0.0) || degrees < 1.0 {
+        return None;
+    }
+    let statistic: f64 = observed
+        .iter()
+        .zip(expected)
+        .map(|(o, e)| (o - e).powi(2) / e)
+        .sum();
+    Some(Test {
+        statistic,
+        p_value: upper_tail("chi-squared", &[degrees], statistic),
+        degrees: vec![degrees],
+    })
+}
+
+/// Chi-squared goodness-of-fit test, whose expected counts are scaled to the total observed
+pub fn chi_squared_fit(observed: &[f64], expected: &[f64]) -> Option<Test> {
+    if observed.len() != expected.len() {
+        return None;
+    }
+    let scale = observed.iter().sum::<f64>() / expected.iter().sum::<f64>();
+    let expected: Vec<f64> = expected.iter().map(|e| e * scale).collect();
+    chi_squared(observed, &expected, observed.len() as f64 - 1.0)
+}
+
+/// Chi-squared test of independence on the contingency table
+pub fn chi_squared_independence(table: &[f64], (rows, cols): (usize, usize)) -> Option<Test> {
+    let row_sums: Vec<f64> = (0..rows)
+        .map(|i| table[i * cols..(i + 1) * cols].iter().sum())
+        .collect();
+    let col_sums: Vec<f64> = (0..cols)
+        .map(|j| (0..rows).map(|i| table[i * cols + j]).sum())
+        .collect();
+    let total: f64 = row_sums.iter().sum();
+    let expected: Vec<f64> = (0..rows * cols)
+        .map(|k| row_sums[k / cols] * col_sums[k % cols] / total)
+        .collect();
+    chi_squared(table, &expected, (rows as f64 - 1.0) * (cols as f64 - 1.0))
+}
+
+/// One-way analysis of variance whether the means of groups are equal
+pub fn anova(groups: &[Vec<f64>]) -> Option<Test> {
+    let k = groups.len();
+    let n: usize = groups.iter().map(|g| g.len()).sum();
+    if k < 2 || n <= k || groups.iter().any(|g| g.is_empty()) {
+        return None;
+    }
+    let grand = groups.iter().flatten().sum::<f64>() / n as f64;
+    let between: f64 = groups
+        .iter()
+        .map(|g| g.len() as f64 * (mean(g) - grand).powi(2))
+        .sum();
+    let within: f64 = groups
+        .iter()
+        .map(|g| {
+            let mean = mean(g);
+            g.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
+        })
+        .sum();
+    let degrees = vec![(k - 1) as f64, (n - k) as f64];
+    let statistic = (between / degrees[0]) / (within / degrees[1]);
+    statistic.is_finite().then(|| Test {
+        statistic,
+        p_value: upper_tail("f", &degrees, statistic),
+        degrees,
+    })
+}
+
+/// Mann–Whitney U test by normal approximation with correction for ties and continuity,
+/// the statistic is U of the first sample
+pub fn mann_whitney(x: &[f64], y: &[f64]) -> Option<Test> {
+    if x.is_empty() || y.is_empty() {
+        return None;
+    }
+    let (n1, n2) = (x.len() as f64, y.len() as f64);
+    let n = n1 + n2;
+    let combined: Vec<f64> = x.iter().chain(y).copied().collect();
+    let ranks = ranks(&combined);
+    let u = ranks[..x.len()].iter().sum::<f64>() - n1 * (n1 + 1.0) / 2.0;
+
+    // Sizes of groups of ties
+    let mut sorted = combined.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let mut ties = 0.0;
+    for group in sorted.chunk_by(|a, b| a == b) {
+        let t = group.len() as f64;
+        ties += t.powi(3) - t;
+    }
+
+    let sigma = (n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)))).sqrt();
+    if sigma == 0.0 || !sigma.is_finite() {
+        return None;
+    }
+    let z = (((u - n1 * n2 / 2.0).abs() - 0.5) / sigma).max(0.0);
+    Some(Test {
+        statistic: u,
+        p_value: two_sided("normal", &[0.0, 1.0], z),
+        degrees: vec![],
+    })
+}
+
+/// Asymptotic probability of Kolmogorov distribution exceeding the scaled statistic
+fn kolmogorov(lambda: f64) -> f64 {
+    if lambda < 0.2 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    for k in 1..=100 {
+        let term = (-2.0 * (k * k) as f64 * lambda * lambda).exp();
+        sum += if k % 2 == 1 { term } else { -term };
+        if term < 1e-16 {
+            break;
+        }
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Test by Kolmogorov–Smirnov statistic of the effective size
+fn kolmogorov_smirnov(statistic: f64, size: f64) -> Test {
+    let root = size.sqrt();
+    Test {
+        statistic,
+        p_value: kolmogorov((root + 0.12 + 0.11 / root) * statistic),
+        degrees: vec![],
+    }
+}
+
+/// One-sample Kolmogorov–Smirnov test against the cumulative distribution function
+pub fn ks_one(x: &[f64], cdf: &dyn Fn(f64) -> f64) -> Option<Test> {
+    if x.is_empty() {
+        return None;
+    }
+    let mut sorted = x.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let n = x.len() as f64;
+    let statistic = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let f = cdf(*x);
+            ((i + 1) as f64 / n - f).max(f - i as f64 / n)
+        })
+        .fold(0.0, f64::max);
+    Some(kolmogorov_smirnov(statistic, n))
+}
+
+/// Two-sample Kolmogorov–Smirnov test whether the samples are from the same distribution
+pub fn ks_two(x: &[f64], y: &[f64]) -> Option<Test> {
+    if x.is_empty() || y.is_empty() {
+        return None;
+    }
+    let sort = |x: &[f64]| {
+        let mut x = x.to_vec();
+        x.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        x
+    };
+    let (x, y) = (sort(x), sort(y));
+    let (n, m) = (x.len() as f64, y.len() as f64);
+
+    // Largest gap of the empirical distribution functions
+    let (mut i, mut j) = (0, 0);
+    let mut statistic: f64 = 0.0;
+    while i < x.len() && j < y.len() {
+        let value = x[i].min(y[j]);
+        while i < x.len() && x[i] == value {
+            i += 1;
+        }
+        while j < y.len() && y[j] == value {
+            j += 1;
+        }
+        statistic = statistic.max((i as f64 / n - j as f64 / m).abs());
+    }
+    Some(kolmogorov_smirnov(statistic, n * m / (n + m)))
+}
+
+/// Test whether Pearson's correlation coefficient is zero by t distribution
+pub fn pearson(x: &[f64], y: &[f64]) -> Option<Test> {
+    if x.len() != y.len() || x.len() < 3 {
+        return None;
+    }
+    let (mean_x, mean_y) = (mean(x), mean(y));
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y) {
+        sxy += (a - mean_x) * (b - mean_y);
+        sxx += (a - mean_x).powi(2);
+        syy += (b - mean_y).powi(2);
+    }
+    let r = (sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0);
+    if !r.is_finite() {
+        return None;
+    }
+    let degrees = (x.len() - 2) as f64;
+    let t = r * (degrees / (1.0 - r * r)).sqrt();
+    Some(Test {
+        statistic: r,
+        p_value: if t.is_finite() {
+            two_sided("t", &[degrees], t)
+        } else {
+            0.0
+        },
+        degrees: vec![degrees],
+    })
+}
+
+/// Test whether Spearman's rank correlation coefficient is zero by t distribution
+pub fn spearman(x: &[f64], y: &[f64]) -> Option<Test> {
+    pearson(&ranks(x), &ranks(y))
+}
@@ -0,0 +1,167 @@
+use crate::polynomial::Polynomial;
+use crate::{Fraction, Type};
+
+/// Value at the point by Lagrange's formula
+pub fn lagrange(xs: &[Fraction], ys: &[Fraction], x: Fraction) -> Fraction {
+    let mut sum = Fraction::new(0.0);
+    for (i, (xi, yi)) in xs.iter().zip(ys).enumerate() {
+        let mut term = *yi;
+        for (j, xj) in xs.iter().enumerate() {
+            if i != j {
+                term = term * (x - *xj) / (*xi - *xj);
+            }
+        }
+        sum = sum + term;
+    }
+    sum
+}
+
+/// Interpolating polynomial as sum of Lagrange basis polynomials
+pub fn lagrange_polynomial(xs: &[Fraction], ys: &[Fraction]) -> Polynomial {
+    let mut sum = Polynomial::new(vec![]);
+    for (i, (xi, yi)) in xs.iter().zip(ys).enumerate() {
+        let mut basis = Polynomial::constant(*yi);
+        for (j, xj) in xs.iter().enumerate() {
+            if i != j {
+                let scale = Fraction::new(1.0) / (*xi - *xj);
+                basis = basis * Polynomial::new(vec![scale, Fraction::new(0.0) - *xj * scale]);
+            }
+        }
+        sum = sum + basis;
+    }
+    sum
+}
+
+/// Coefficients of Newton's form from the table of divided differences
+pub fn divided_differences(xs: &[Fraction], ys: &[Fraction]) -> Vec<Fraction> {
+    let mut table = ys.to_vec();
+    let mut coefficients = vec![table[0]];
+    for k in 1..xs.len() {
+        table = (0..table.len() - 1)
+            .map(|i| (table[i + 1] - table[i]) / (xs[i + k] - xs[i]))
+            .collect();
+        coefficients.push(table[0]);
+    }
+    coefficients
+}
+
+/// Value at the point of Newton's form by nested multiplication
+pub fn newton(xs: &[Fraction], coefficients: &[Fraction], x: Fraction) -> Fraction {
+    coefficients
+        .iter()
+        .zip(xs)
+        .rev()
+        .fold(Fraction::new(0.0), |acc, (c, xi)| acc * (x - *xi) + *c)
+}
+
+/// Expand Newton's form into polynomial
+pub fn newton_polynomial(xs: &[Fraction], coefficients: &[Fraction]) -> Polynomial {
+    coefficients
+        .iter()
+        .zip(xs)
+        .rev()
+        .fold(Polynomial::new(vec![]), |acc, (c, xi)| {
+            acc * Polynomial::new(vec![Fraction::new(1.0), Fraction::new(0.0) - *xi])
+                + Polynomial::constant(*c)
+        })
+}
+
+/// Piecewise polynomial interpolant
+#[derive(Clone, Debug)]
+pub struct Interpolant {
+    knots: Vec<Fraction>,    // Sorted x coordinates of the samples
+    pieces: Vec<Polynomial>, // Polynomial of x - knots[i] on each interval
+}
+
+impl Interpolant {
+    /// Connect the samples by straight lines
+    pub fn linear(xs: &[Fraction], ys: &[Fraction]) -> Interpolant {
+        let pieces = (0..xs.len() - 1)
+            .map(|i| {
+                let slope = (ys[i + 1] - ys[i]) / (xs[i + 1] - xs[i]);
+                Polynomial::new(vec![slope, ys[i]])
+            })
+            .collect();
+        Interpolant {
+            knots: xs.to_vec(),
+            pieces,
+        }
+    }
+
+    /// Cubic spline, natural without slopes or clamped to the slopes at both ends
+    pub fn cubic_spline(
+        xs: &[Fraction],
+        ys: &[Fraction],
+        slopes: Option<(Fraction, Fraction)>,
+    ) -> Interpolant {
+        let n = xs.len() - 1;
+        let zero = Fraction::new(0.0);
+        let (two, three) = (Fraction::new(2.0), Fraction::new(3.0));
+        let h: Vec<Fraction> = (0..n).map(|i| xs[i + 1] - xs[i]).collect();
+        let secant: Vec<Fraction> = (0..n).map(|i| (ys[i + 1] - ys[i]) / h[i]).collect();
+
+        // Tridiagonal system of half the second derivatives at the knots
+        let mut lower = vec![zero; n + 1];
+        let mut diagonal = vec![Fraction::new(1.0); n + 1];
+        let mut upper = vec![zero; n + 1];
+        let mut rhs = vec![zero; n + 1];
+        for i in 1..n {
+            lower[i] = h[i - 1];
+            diagonal[i] = two * (h[i - 1] + h[i]);
+            upper[i] = h[i];
+            rhs[i] = three * (secant[i] - secant[i - 1]);
+        }
+        if let Some((start, end)) = slopes {
+            diagonal[0] = two * h[0];
+            upper[0] = h[0];
+            rhs[0] = three * (secant[0] - start);
+            lower[n] = h[n - 1];
+            diagonal[n] = two * h[n - 1];
+            rhs[n] = three * (end - secant[n - 1]);
+        }
+
+        // Thomas algorithm
+        for i in 1..=n {
+            let factor = lower[i] / diagonal[i - 1];
+            diagonal[i] = diagonal[i] - factor * upper[i - 1];
+            rhs[i] = rhs[i] - factor * rhs[i - 1];
+        }
+        let mut c = vec![zero; n + 1];
+        c[n] = rhs[n] / diagonal[n];
+        for i in (0..n).rev() {
+            c[i] = (rhs[i] - upper[i] * c[i + 1]) / diagonal[i];
+        }
+
+        let pieces = (0..n)
+            .map(|i| {
+                let b = secant[i] - h[i] * (two * c[i] + c[i + 1]) / three;
+                let d = (c[i + 1] - c[i]) / (three * h[i]);
+                Polynomial::new(vec![d, c[i], b, ys[i]])
+            })
+            .collect();
+        Interpolant {
+            knots: xs.to_vec(),
+            pieces,
+        }
+    }
+
+    /// Value at the point, the end pieces extrapolate outside the knots
+    pub fn eval(&self, x: Fraction) -> Fraction {
+        let value = x.to_f64();
+        let i = self.knots[1..self.pieces.len()].partition_point(|k| k.to_f64() <= value);
+        self.pieces[i].eval(x - self.knots[i])
+    }
+
+    pub fn display(&self) -> String {
+        let knots = self.knots.iter().map(|x| Type::Number(*x)).collect();
+        let pieces = self
+            .pieces
+            .iter()
+            .map(|p| Type::Polynomial(p.clone()))
+            .collect();
+        format!(
+            "interpolant:{}",
+            Type::List(vec![Type::List(knots), Type::List(pieces)]).display()
+        )
+    }
+}
@@ -0,0 +1,218 @@
+use crate::Fraction;
+
+/// Maximum count of subproblems in branch and bound
+const MAX_NODES: usize = 10_000;
+
+/// Result of linear programming
+pub enum Outcome {
+    Optimal(Fraction, Vec<Fraction>), // Optimum and the solution
+    Infeasible,
+    Unbounded,
+}
+
+/// Sign of the fraction whose denominator may be negative
+fn sign(x: Fraction) -> isize {
+    x.numerator.signum() * x.denominator.signum()
+}
+
+/// Simplex tableau whose last column is the right-hand side
+struct Tableau {
+    rows: Vec<Vec<Fraction>>,
+    basis: Vec<usize>, // Basic variable of each row
+}
+
+impl Tableau {
+    /// Make the column basic in the row by elimination
+    fn pivot(&mut self, row: usize, column: usize) {
+        let factor = self.rows[row][column];
+        for x in self.rows[row].iter_mut() {
+            *x = *x / factor;
+        }
+        let pivot_row = self.rows[row].clone();
+        for (i, other) in self.rows.iter_mut().enumerate() {
+            let factor = other[column];
+            if i != row && factor.numerator != 0 {
+                for (x, p) in other.iter_mut().zip(&pivot_row) {
+                    *x = *x - factor * *p;
+                }
+            }
+        }
+        self.basis[row] = column;
+    }
+
+    /// Maximize the objective over the first columns by Bland's rule against cycling,
+    /// returns false if it is unbounded
+    fn maximize(&mut self, objective: &[Fraction], columns: usize) -> bool {
+        let rhs = self.rows.first().map_or(0, |row| row.len() - 1);
+        loop {
+            // Reduced cost of each column
+            let entering = (0..columns).find(|j| {
+                let reduced = self
+                    .basis
+                    .iter()
+                    .zip(&self.rows)
+                    .fold(objective[*j], |acc, (b, row)| acc - objective[*b] * row[*j]);
+                sign(reduced) > 0
+            });
+            let column = match entering {
+                Some(column) => column,
+                None => return true,
+            };
+
+            // Minimum ratio test, ties broken by the smallest basic variable
+            let mut leaving: Option<(usize, Fraction)> = None;
+            for (i, row) in self.rows.iter().enumerate() {
+                if sign(row[column]) > 0 {
+                    let ratio = row[rhs] / row[column];
+                    let better = match leaving {
+                        None => true,
+                        Some((l, best)) => match sign(ratio - best) {
+                            -1 => true,
+                            0 => self.basis[i] < self.basis[l],
+                            _ => false,
+                        },
+                    };
+                    if better {
+                        leaving = Some((i, ratio));
+                    }
+                }
+            }
+            match leaving {
+                Some((row, _)) => self.pivot(row, column),
+                None => return false,
+            }
+        }
+    }
+}
+
+/// Maximize c·x subject to Ax <= b and x >= 0 by two-phase simplex method
+pub fn simplex(c: &[Fraction], a: &[Vec<Fraction>], b: &[Fraction]) -> Outcome {
+    let (m, n) = (a.len(), c.len());
+    let zero = Fraction::new(0.0);
+    let one = Fraction::new(1.0);
+
+    // Columns of variables, slacks, artificials and right-hand side
+    let artificials: Vec<usize> = (0..m).filter(|i| sign(b[*i]) < 0).collect();
+    let width = n + m + artificials.len();
+    let mut tableau = Tableau {
+        rows: Vec::new(),
+        basis: Vec::new(),
+    };
+    for i in 0..m {
+        let mut row = vec![zero; width + 1];
+        row[..n].copy_from_slice(&a[i]);
+        row[n + i] = one;
+        row[width] = b[i];
+        match artificials.iter().position(|x| *x == i) {
+            Some(k) => {
+                // Negate the row to make the right-hand side nonnegative
+                for x in row.iter_mut() {
+                    *x = zero - *x;
+                }
+                row[n + m + k] = one;
+                tableau.basis.push(n + m + k);
+            }
+            None => tableau.basis.push(n + i),
+        }
+        tableau.rows.push(row);
+    }
+
+    // Phase one minimizes sum of the artificials
+    if !artificials.is_empty() {
+        let mut objective = vec![zero; width];
+        for x in objective[n + m..].iter_mut() {
+            *x = zero - one;
+        }
+        tableau.maximize(&objective, width);
+        let infeasibility = tableau
+            .basis
+            .iter()
+            .zip(&tableau.rows)
+            .filter(|(b, _)| **b >= n + m)
+            .fold(zero, |acc, (_, row)| acc + row[width]);
+        if sign(infeasibility) != 0 {
+            return Outcome::Infeasible;
+        }
+
+        // Drive artificials at zero out of the basis
+        for i in 0..m {
+            if tableau.basis[i] >= n + m {
+                if let Some(column) = (0..n + m).find(|j| sign(tableau.rows[i][*j]) != 0) {
+                    tableau.pivot(i, column);
+                }
+            }
+        }
+    }
+
+    // Phase two
+    let mut objective = c.to_vec();
+    objective.resize(width, zero);
+    if !tableau.maximize(&objective, n + m) {
+        return Outcome::Unbounded;
+    }
+
+    let mut solution = vec![zero; n];
+    for (b, row) in tableau.basis.iter().zip(&tableau.rows) {
+        if *b < n {
+            solution[*b] = row[width];
+        }
+    }
+    let optimum = c
+        .iter()
+        .zip(&solution)
+        .fold(zero, |acc, (c, x)| acc + *c * *x);
+    Outcome::Optimal(optimum, solution)
+}
+
+/// Maximize over integers by branch and bound on the relaxation,
+/// fails if the subproblems exceed the limit
+pub fn branch_and_bound(c: &[Fraction], a: &[Vec<Fraction>], b: &[Fraction]) -> Option<Outcome> {
+    let mut best: Option<(Fraction, Vec<Fraction>)> = None;
+    let mut problems = vec![(a.to_vec(), b.to_vec())];
+    let mut nodes = 0;
+
+    while let Some((a, b)) = problems.pop() {
+        nodes += 1;
+        if nodes > MAX_NODES {
+            return None;
+        }
+        let (value, solution) = match simplex(c, &a, &b) {
+            Outcome::Optimal(value, solution) => (value, solution),
+            Outcome::Infeasible => continue,
+            Outcome::Unbounded => return Some(Outcome::Unbounded),
+        };
+        // Relaxation cannot beat the incumbent
+        if best.as_ref().is_some_and(|(v, _)| sign(value - *v) <= 0) {
+            continue;
+        }
+
+        let fractional = solution.iter().position(|x| x.denominator.abs() != 1);
+        match fractional {
+            None => best = Some((value, solution)),
+            Some(i) => {
+                let x = solution[i];
+                let floor = (x.numerator as f64 / x.denominator as f64).floor();
+                let mut row = vec![Fraction::new(0.0); c.len()];
+
+                // x_i <= floor
+                row[i] = Fraction::new(1.0);
+                let (mut a_low, mut b_low) = (a.clone(), b.clone());
+                a_low.push(row.clone());
+                b_low.push(Fraction::new(floor));
+
+                // -x_i <= -(floor + 1)
+                row[i] = Fraction::new(-1.0);
+                let (mut a_high, mut b_high) = (a, b);
+                a_high.push(row);
+                b_high.push(Fraction::new(-(floor + 1.0)));
+
+                problems.push((a_high, b_high));
+                problems.push((a_low, b_low));
+            }
+        }
+    }
+    Some(match best {
+        Some((value, solution)) => Outcome::Optimal(value, solution),
+        None => Outcome::Infeasible,
+    })
+}
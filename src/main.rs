@@ -1,8 +1,32 @@
+mod calculus;
+mod distribution;
+mod flow;
+mod fourier;
+mod graph;
+mod hypothesis;
+mod interpolate;
+mod linprog;
+mod ndarray;
+mod ode;
+mod optimize;
+mod plot;
+mod polynomial;
+mod regression;
+mod roots;
+mod series;
+mod statistics;
+mod table;
+
 use clap::{App, Arg};
 use gnuplot::Figure;
-use petgraph::dot::{Config, Dot};
-use petgraph::Graph;
+use graph::Network;
+use interpolate::Interpolant;
+use ndarray::NdArray;
+use polynomial::Polynomial;
+use rand::distributions::{Distribution as _, WeightedIndex};
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 use regex::Regex;
 use std::collections::HashMap;
 use std::env;
@@ -12,6 +36,7 @@ use std::ops::{Add, Div, Mul, Sub};
 use std::path::Path;
 use std::thread::{self, sleep};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use table::Table;
 
 fn main() {
     let app = App::new("NumStack")
@@ -26,12 +51,26 @@ fn main() {
         .arg(Arg::new("debug")
             .short('d')
             .long("debug")
-            .help("Enables debug mode"));
+            .help("Enables debug mode"))
+        .arg(Arg::new("seed")
+            .long("seed")
+            .value_name("NUMBER")
+            .help("Sets the seed of random number generator for reproducible results")
+            .takes_value(true));
     let matches = app.clone().get_matches();
 
+    let seed = match matches.value_of("seed").map(|seed| seed.parse::<u64>()) {
+        Some(Ok(seed)) => Some(seed),
+        Some(Err(err)) => {
+            println!("Error! seed is invalid: {err}");
+            return;
+        }
+        None => None,
+    };
+
     if let Some(script) = matches.value_of("script") {
         if matches.is_present("debug") {
-            let mut stack = Executor::new(Mode::Debug);
+            let mut stack = Executor::new(Mode::Debug, seed);
             stack.evaluate_program(match get_file_contents(Path::new(&script.to_string())) {
                 Ok(code) => code,
                 Err(err) => {
@@ -40,7 +79,7 @@ fn main() {
                 }
             })
         } else {
-            let mut stack = Executor::new(Mode::Script);
+            let mut stack = Executor::new(Mode::Script, seed);
             stack.evaluate_program(match get_file_contents(Path::new(&script.to_string())) {
                 Ok(code) => code,
                 Err(err) => {
@@ -53,7 +92,7 @@ fn main() {
         // Show a title
         println!("NumStack Programming Language");
         println!("Version {}", { app.get_version().unwrap_or("unknown") });
-        let mut executor = Executor::new(Mode::Debug);
+        let mut executor = Executor::new(Mode::Debug, seed);
         // REPL Execution
         loop {
             let mut code = String::new();
@@ -70,6 +109,16 @@ fn main() {
     }
 }
 
+/// List of node indices
+fn node_list(nodes: Vec<usize>) -> Type {
+    Type::List(
+        nodes
+            .into_iter()
+            .map(|n| Type::Number(Fraction::new(n as f64)))
+            .collect(),
+    )
+}
+
 /// Read string of the file
 fn get_file_contents(name: &Path) -> Result<String, Error> {
     let mut f = File::open(name)?;
@@ -90,31 +139,45 @@ fn input(prompt: &str) -> String {
 fn convert(number: f64) -> (isize, isize) {
     const MAX_DENOMINATOR: isize = 1_000_000;
 
-    if number == 0.0 {
+    if number == 0.0 || !number.is_finite() {
         return (0, 1);
     }
+    if number.abs() >= isize::MAX as f64 {
+        return (number.signum() as isize * isize::MAX, 1);
+    }
 
-    let mut best_numerator = 0;
-    let mut best_denominator = 1;
-    let mut best_diff = f64::MAX;
-
-    for denominator in 1..=MAX_DENOMINATOR {
-        let numerator = (number * denominator as f64).round() as isize;
-        let fraction = numerator as f64 / denominator as f64;
-        let diff = (number - fraction).abs();
-
-        if diff < best_diff {
-            best_diff = diff;
-            best_numerator = numerator;
-            best_denominator = denominator;
+    // Expand into continued fraction until the denominator exceeds the limit
+    let (mut p0, mut q0, mut p1, mut q1) = (0, 1, 1, 0);
+    let mut x = number;
+    loop {
+        let a = x.floor();
+        if q0 as f64 + a * q1 as f64 > MAX_DENOMINATOR as f64 {
+            break;
+        }
+        let p2 = match (a as isize).checked_mul(p1).and_then(|x| x.checked_add(p0)) {
+            Some(p2) => p2,
+            None => break,
+        };
+        let a = a as isize;
+        (p0, q0, p1, q1) = (p1, q1, p2, q0 + a * q1);
 
-            if diff == 0.0 {
-                break;
-            }
+        if x == a as f64 || p1 as f64 / q1 as f64 == number {
+            break;
         }
+        x = 1.0 / (x - a as f64);
     }
 
-    (best_numerator, best_denominator)
+    // The best approximation is the last convergent or the semiconvergent
+    let k = (MAX_DENOMINATOR - q0) / q1;
+    match k.checked_mul(p1).and_then(|x| x.checked_add(p0)) {
+        Some(p2)
+            if (number - p2 as f64 / (q0 + k * q1) as f64).abs()
+                < (number - p1 as f64 / q1 as f64).abs() =>
+        {
+            (p2, q0 + k * q1)
+        }
+        _ => (p1, q1),
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -138,7 +201,7 @@ impl Fraction {
     fn from(value: String) -> Fraction {
         let value = value.split("/").collect::<Vec<&str>>();
         let mut fraction = Fraction {
-            numerator: value.get(0).unwrap_or(&"0").parse::<isize>().unwrap_or(0),
+            numerator: value.first().unwrap_or(&"0").parse::<isize>().unwrap_or(0),
             denominator: value.get(1).unwrap_or(&"1").parse::<isize>().unwrap_or(1),
         };
         fraction.simplify();
@@ -166,14 +229,52 @@ impl Fraction {
             a
         }
 
+        // Undefined 0/0, from arithmetic of infinite 1/0 like unreachable distance, is kept
         let gcd = gcd(self.numerator.abs(), self.denominator.abs());
+        if gcd == 0 {
+            return;
+        }
         self.numerator /= gcd;
         self.denominator /= gcd;
     }
 
+    // Function to make the fraction from result of wide arithmetic,
+    // approximating it if the exact value does not fit
+    fn reduce(numerator: i128, denominator: i128) -> Fraction {
+        Fraction::exact(numerator, denominator)
+            .unwrap_or_else(|| Fraction::new(numerator as f64 / denominator as f64))
+    }
+
+    // Function to make the fraction from result of wide arithmetic,
+    // or nothing if the exact value does not fit
+    fn exact(numerator: i128, denominator: i128) -> Option<Fraction> {
+        fn gcd(mut a: i128, mut b: i128) -> i128 {
+            while b != 0 {
+                let temp = b;
+                b = a % b;
+                a = temp;
+            }
+            a
+        }
+
+        // Keep the denominator positive
+        let mut gcd = gcd(numerator.abs(), denominator.abs()).max(1);
+        if denominator < 0 {
+            gcd = -gcd;
+        }
+        let (numerator, denominator) = (numerator / gcd, denominator / gcd);
+        match (isize::try_from(numerator), isize::try_from(denominator)) {
+            (Ok(numerator), Ok(denominator)) if numerator != isize::MIN => Some(Fraction {
+                numerator,
+                denominator,
+            }),
+            _ => None,
+        }
+    }
+
     // Function to convert the fraction to a floating-point number
-    fn to_f64(&self) -> f64 {
-        (self.numerator / self.denominator) as f64
+    fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
     }
 }
 
@@ -181,30 +282,11 @@ impl Add for Fraction {
     type Output = Fraction;
 
     fn add(self, other: Fraction) -> Fraction {
-        let number = other;
-        fn lcm(a: isize, b: isize) -> isize {
-            let gcd = |mut a: isize, mut b: isize| {
-                while b != 0 {
-                    let temp = b;
-                    b = a % b;
-                    a = temp;
-                }
-                a
-            };
-
-            (a * b) / gcd(a, b)
-        }
-
-        let denominator = lcm(self.denominator, number.denominator);
-        let numerator1 = self.numerator * (denominator / self.denominator);
-        let numerator2 = other.numerator * (denominator / other.denominator);
-
-        let mut result = Fraction {
-            numerator: numerator1 + numerator2,
-            denominator,
-        };
-        result.simplify();
-        result
+        Fraction::reduce(
+            self.numerator as i128 * other.denominator as i128
+                + other.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
     }
 }
 
@@ -212,30 +294,11 @@ impl Sub for Fraction {
     type Output = Fraction;
 
     fn sub(self, other: Fraction) -> Fraction {
-        let number = other;
-        fn lcm(a: isize, b: isize) -> isize {
-            let gcd = |mut a: isize, mut b: isize| {
-                while b != 0 {
-                    let temp = b;
-                    b = a % b;
-                    a = temp;
-                }
-                a
-            };
-
-            (a * b) / gcd(a, b)
-        }
-
-        let denominator = lcm(self.denominator, number.denominator);
-        let numerator1 = self.numerator * (denominator / self.denominator);
-        let numerator2 = other.numerator * (denominator / other.denominator);
-
-        let mut result = Fraction {
-            numerator: numerator1 - numerator2,
-            denominator,
-        };
-        result.simplify();
-        result
+        Fraction::reduce(
+            self.numerator as i128 * other.denominator as i128
+                - other.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
     }
 }
 
@@ -243,13 +306,10 @@ impl Mul for Fraction {
     type Output = Fraction;
 
     fn mul(self, other: Fraction) -> Fraction {
-        let number = other;
-        let mut result = Fraction {
-            numerator: self.numerator * number.numerator,
-            denominator: self.denominator * number.denominator,
-        };
-        result.simplify();
-        result
+        Fraction::reduce(
+            self.numerator as i128 * other.numerator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
     }
 }
 
@@ -257,12 +317,10 @@ impl Div for Fraction {
     type Output = Fraction;
 
     fn div(self, other: Fraction) -> Fraction {
-        let mut number = other;
-        (number.denominator, number.numerator) = (number.numerator, number.denominator);
-
-        let mut result = self * number;
-        result.simplify();
-        result
+        Fraction::reduce(
+            self.numerator as i128 * other.denominator as i128,
+            self.denominator as i128 * other.numerator as i128,
+        )
     }
 }
 
@@ -281,6 +339,11 @@ enum Type {
     Bool(bool),
     List(Vec<Type>),
     Matrix(Vec<Fraction>, (usize, usize)),
+    Array(NdArray),
+    Polynomial(Polynomial),
+    Interpolant(Interpolant),
+    Table(Table),
+    Graph(Network),
     Error(String),
 }
 
@@ -293,13 +356,13 @@ impl Type {
         let mut count = 0;
         for i in mx {
             if count < length {
-                buffer.push(i.clone());
+                buffer.push(*i);
                 count += 1;
             } else {
                 matrix.push(buffer.clone());
                 buffer.clear();
                 count = 1;
-                buffer.push(i.clone());
+                buffer.push(*i);
             }
         }
         matrix.push(buffer.clone());
@@ -331,6 +394,11 @@ impl Type {
             }
             Type::Error(err) => format!("error:{err}"),
             Type::Matrix(mx, (_, length)) => Type::to_matrix(mx, *length),
+            Type::Array(array) => array.display(),
+            Type::Polynomial(poly) => poly.display(),
+            Type::Interpolant(interpolant) => interpolant.display(),
+            Type::Table(table) => table.display(),
+            Type::Graph(network) => network.display(),
         }
     }
 
@@ -343,6 +411,11 @@ impl Type {
             Type::List(l) => Type::List(l.to_owned()).display(),
             Type::Error(err) => format!("error:{err}"),
             Type::Matrix(mx, (_, length)) => Type::to_matrix(mx, *length),
+            Type::Array(array) => array.display(),
+            Type::Polynomial(poly) => poly.display(),
+            Type::Interpolant(interpolant) => interpolant.display(),
+            Type::Table(table) => table.display(),
+            Type::Graph(network) => network.display(),
         }
     }
 
@@ -350,7 +423,7 @@ impl Type {
     fn get_number(&mut self) -> Fraction {
         match self {
             Type::String(s) => Fraction::from(s.to_owned()),
-            Type::Number(i) => i.clone(),
+            Type::Number(i) => *i,
             Type::Bool(b) => {
                 if *b {
                     Fraction::new(1.0)
@@ -364,6 +437,37 @@ impl Type {
         }
     }
 
+    /// Apply math function to the number or every element of list, matrix and array
+    fn map_number(self, f: fn(f64) -> f64) -> Type {
+        match self {
+            Type::List(list) => Type::List(list.into_iter().map(|x| x.map_number(f)).collect()),
+            Type::Matrix(mx, size) => Type::Matrix(
+                mx.iter().map(|x| Fraction::new(f(x.to_f64()))).collect(),
+                size,
+            ),
+            Type::Array(array) => Type::Array(array.map(|x| Fraction::new(f(x.to_f64())))),
+            mut other => Type::Number(Fraction::new(f(other.get_number().to_f64()))),
+        }
+    }
+
+    /// Apply exact function to the number or every element of list, matrix and array
+    fn map_exact(self, f: &dyn Fn(Fraction) -> Fraction) -> Type {
+        match self {
+            Type::List(list) => Type::List(list.into_iter().map(|x| x.map_exact(f)).collect()),
+            Type::Matrix(mx, size) => Type::Matrix(mx.into_iter().map(f).collect(), size),
+            Type::Array(array) => Type::Array(array.map(f)),
+            mut other => Type::Number(f(other.get_number())),
+        }
+    }
+
+    /// Get number as bound of range, a string like (inf) is infinity
+    fn get_bound(&mut self) -> f64 {
+        match self {
+            Type::String(s) if s.trim().parse::<f64>().is_ok() => s.trim().parse().unwrap_or(0.0),
+            other => other.get_number().to_f64(),
+        }
+    }
+
     /// Get bool from data
     fn get_bool(&mut self) -> bool {
         match self {
@@ -371,6 +475,7 @@ impl Type {
             Type::Number(i) => i.to_f64() != 0.0,
             Type::Bool(b) => *b,
             Type::List(l) => !l.is_empty(),
+            Type::Array(array) => array.size() != 0,
             Type::Error(e) => e.parse().unwrap_or(false),
             _ => false,
         }
@@ -388,20 +493,52 @@ impl Type {
             Type::Bool(b) => vec![Type::Bool(*b)],
             Type::List(l) => l.to_vec(),
             Type::Error(e) => vec![Type::Error(e.to_string())],
-            Type::Matrix(l, _) => l
-                .to_owned()
-                .iter()
-                .map(|x| Type::Number(x.clone()))
-                .collect(),
+            Type::Matrix(l, _) => l.to_owned().iter().map(|x| Type::Number(*x)).collect(),
+            Type::Array(array) => match array.to_list() {
+                Type::List(l) => l,
+                scalar => vec![scalar],
+            },
+            Type::Polynomial(poly) => poly.coefficients().into_iter().map(Type::Number).collect(),
+            Type::Interpolant(interpolant) => vec![Type::Interpolant(interpolant.clone())],
+            Type::Table(table) => table.to_list(),
+            Type::Graph(network) => vec![Type::Graph(network.clone())],
         }
     }
 
     fn get_matrix(&mut self) -> (Vec<Fraction>, (usize, usize)) {
         match self {
             Type::Matrix(mx, size) => (mx.to_vec(), *size),
+            Type::Array(array) if array.ndim() == 2 => {
+                (array.elements(), (array.shape()[0], array.shape()[1]))
+            }
+            Type::Table(table) => table.to_matrix(),
+            Type::Graph(network) => {
+                let matrix = network.adjacency_matrix();
+                let data = matrix.into_iter().map(Fraction::new).collect();
+                (data, (network.len(), network.len()))
+            }
             _ => (vec![], (0, 0)),
         }
     }
+
+    /// Get N-dimensional array from data, fails if a list is ragged
+    fn get_array(&mut self) -> Option<NdArray> {
+        match self {
+            Type::Array(array) => Some(array.clone()),
+            Type::Matrix(mx, size) => Some(NdArray::from_matrix(mx.to_vec(), *size)),
+            Type::List(l) => NdArray::from_list(l),
+            other => Some(NdArray::scalar(other.get_number())),
+        }
+    }
+
+    /// Get polynomial from data, a list gives coefficients of the highest power first
+    fn get_polynomial(&mut self) -> Polynomial {
+        match self {
+            Type::Polynomial(poly) => poly.clone(),
+            Type::List(l) => Polynomial::new(l.iter_mut().map(|x| x.get_number()).collect()),
+            other => Polynomial::constant(other.get_number()),
+        }
+    }
 }
 
 /// Manage program execution
@@ -410,15 +547,20 @@ struct Executor {
     stack: Vec<Type>,              // Data stack
     memory: HashMap<String, Type>, // Variable's memory
     mode: Mode,                    // Execution mode
+    rng: StdRng,                   // Random number generator
 }
 
 impl Executor {
     /// Constructor
-    fn new(mode: Mode) -> Executor {
+    fn new(mode: Mode, seed: Option<u64>) -> Executor {
         Executor {
             stack: Vec::new(),
             memory: HashMap::new(),
             mode,
+            rng: match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            },
         }
     }
 
@@ -646,13 +788,28 @@ impl Executor {
                     .len();
 
                 let value = text
-                    .split(|c| c == ',' || c == ';')
+                    .split([',', ';'])
                     .map(|x| {
                         self.evaluate_program(x.to_string());
                         self.pop_stack().get_number()
                     })
                     .collect::<Vec<Fraction>>();
                 self.stack.push(Type::Matrix(value, (row, col)))
+            } else if let Some(literal) = token.strip_prefix("array:") {
+                // Push N-dimensional array from nested list literal
+                self.evaluate_program(literal.to_string());
+                match self.pop_stack().get_array() {
+                    Some(array) => self.stack.push(Type::Array(array)),
+                    None => {
+                        self.log_print("Error! array literal is ragged\n".to_string());
+                        self.stack.push(Type::Error("ragged-array".to_string()));
+                    }
+                }
+            } else if let Some(literal) = token.strip_prefix("poly:") {
+                // Push polynomial from list literal of coefficients
+                self.evaluate_program(literal.to_string());
+                let poly = self.pop_stack().get_polynomial();
+                self.stack.push(Type::Polynomial(poly));
             } else if token.starts_with("error:") {
                 // Push error value on the stack
                 self.stack.push(Type::Error(token.replace("error:", "")))
@@ -678,79 +835,129 @@ impl Executor {
 
     /// execute string as commands
     fn execute_command(&mut self, command: String) {
+        // Commands of probability distributions like normal-cdf
+        if let Some((name, function)) = distribution::parse_command(&command) {
+            self.execute_distribution(name, function);
+            return;
+        }
+
         match command.as_str() {
             // Commands of calculation
 
             // Addition
             "add" => {
-                let b = self.pop_stack().get_number();
-                let a = self.pop_stack().get_number();
-                self.stack.push(Type::Number(a + b));
+                let b = self.pop_stack();
+                let a = self.pop_stack();
+                let result = match (a, b) {
+                    (mut a @ Type::Polynomial(_), mut b) | (mut a, mut b @ Type::Polynomial(_)) => {
+                        Type::Polynomial(a.get_polynomial() + b.get_polynomial())
+                    }
+                    (a, b) => self.arithmetic(a, b, |a, b| a + b),
+                };
+                self.stack.push(result);
             }
 
             // Subtraction
             "sub" => {
-                let b = self.pop_stack().get_number();
-                let a = self.pop_stack().get_number();
-                self.stack.push(Type::Number(a - b));
+                let b = self.pop_stack();
+                let a = self.pop_stack();
+                let result = match (a, b) {
+                    (mut a @ Type::Polynomial(_), mut b) | (mut a, mut b @ Type::Polynomial(_)) => {
+                        Type::Polynomial(a.get_polynomial() - b.get_polynomial())
+                    }
+                    (a, b) => self.arithmetic(a, b, |a, b| a - b),
+                };
+                self.stack.push(result);
             }
 
             // Multiplication
             "mul" => {
-                let b = self.pop_stack().get_number();
-                let a = self.pop_stack().get_number();
-                self.stack.push(Type::Number(a * b));
+                let b = self.pop_stack();
+                let a = self.pop_stack();
+                let result = match (a, b) {
+                    (mut a @ Type::Polynomial(_), mut b) | (mut a, mut b @ Type::Polynomial(_)) => {
+                        Type::Polynomial(a.get_polynomial() * b.get_polynomial())
+                    }
+                    (a, b) => self.arithmetic(a, b, |a, b| a * b),
+                };
+                self.stack.push(result);
             }
 
             // Division
             "div" => {
-                let b = self.pop_stack().get_number();
-                let a = self.pop_stack().get_number();
-                self.stack.push(Type::Number(a / b));
+                let b = self.pop_stack();
+                let a = self.pop_stack();
+                let result = match (a, b) {
+                    (mut a @ Type::Polynomial(_), mut b) | (mut a, mut b @ Type::Polynomial(_)) => {
+                        match a.get_polynomial().div_rem(&b.get_polynomial()) {
+                            Some((quotient, _)) => Type::Polynomial(quotient),
+                            None => {
+                                self.log_print("Error! division by zero polynomial\n".to_string());
+                                Type::Error("division-by-zero".to_string())
+                            }
+                        }
+                    }
+                    (a, b) => self.arithmetic(a, b, |a, b| a / b),
+                };
+                self.stack.push(result);
             }
 
             // Remainder of division
             "mod" => {
-                let b = self.pop_stack().get_number().to_f64();
-                let a = self.pop_stack().get_number().to_f64();
-                self.stack.push(Type::Number(Fraction::new(a % b)));
+                let b = self.pop_stack();
+                let a = self.pop_stack();
+                let result = match (a, b) {
+                    (mut a @ Type::Polynomial(_), mut b) | (mut a, mut b @ Type::Polynomial(_)) => {
+                        match a.get_polynomial().div_rem(&b.get_polynomial()) {
+                            Some((_, remainder)) => Type::Polynomial(remainder),
+                            None => {
+                                self.log_print("Error! division by zero polynomial\n".to_string());
+                                Type::Error("division-by-zero".to_string())
+                            }
+                        }
+                    }
+                    (a, b) => self.arithmetic(a, b, |a, b| Fraction::new(a.to_f64() % b.to_f64())),
+                };
+                self.stack.push(result);
             }
 
             // Exponentiation
             "pow" => {
-                let b = self.pop_stack().get_number().to_f64();
-                let a = self.pop_stack().get_number().to_f64();
-                self.stack.push(Type::Number(Fraction::new(a.powf(b))));
+                let b = self.pop_stack();
+                let a = self.pop_stack();
+                let result =
+                    self.arithmetic(a, b, |a, b| Fraction::new(a.to_f64().powf(b.to_f64())));
+                self.stack.push(result);
             }
 
             // Rounding off
             "round" => {
-                let a = self.pop_stack().get_number().to_f64();
-                self.stack.push(Type::Number(Fraction::new(a.round())));
+                let a = self.pop_stack().map_number(f64::round);
+                self.stack.push(a);
             }
 
             // Trigonometric sine
             "sin" => {
-                let number = self.pop_stack().get_number().to_f64();
-                self.stack.push(Type::Number(Fraction::new(number.sin())))
+                let number = self.pop_stack().map_number(f64::sin);
+                self.stack.push(number)
             }
 
             // Trigonometric cosine
             "cos" => {
-                let number = self.pop_stack().get_number().to_f64();
-                self.stack.push(Type::Number(Fraction::new(number.cos())))
+                let number = self.pop_stack().map_number(f64::cos);
+                self.stack.push(number)
             }
 
             // Trigonometric tangent
             "tan" => {
-                let number = self.pop_stack().get_number().to_f64();
-                self.stack.push(Type::Number(Fraction::new(number.tan())))
+                let number = self.pop_stack().map_number(f64::tan);
+                self.stack.push(number)
             }
 
             // Exponential function
             "exp" => {
-                let number = self.pop_stack().get_number().to_f64();
-                self.stack.push(Type::Number(Fraction::new(number.exp())))
+                let number = self.pop_stack().map_number(f64::exp);
+                self.stack.push(number)
             }
 
             // Logical operations of AND
@@ -790,7 +997,7 @@ impl Executor {
             // Get random value from list
             "rand" => {
                 let list = self.pop_stack().get_list();
-                let result = match list.choose(&mut rand::thread_rng()) {
+                let result = match list.choose(&mut self.rng) {
                     Some(i) => i.to_owned(),
                     None => Type::List(list),
                 };
@@ -800,10 +1007,113 @@ impl Executor {
             // Shuffle list by random
             "shuffle" => {
                 let mut list = self.pop_stack().get_list();
-                list.shuffle(&mut rand::thread_rng());
+                list.shuffle(&mut self.rng);
                 self.stack.push(Type::List(list));
             }
 
+            // Reset random number generator by the seed to reproduce results,
+            // which is given as string like (18446744073709551615) beyond exact integers of float
+            "seed" => {
+                const MAX_EXACT: isize = 1 << 53;
+                let seed = match self.pop_stack() {
+                    Type::Number(n)
+                        if n.denominator == 1 && (0..=MAX_EXACT).contains(&n.numerator) =>
+                    {
+                        Some(n.numerator as u64)
+                    }
+                    Type::String(s) => s.trim().parse::<u64>().ok(),
+                    _ => None,
+                };
+                match seed {
+                    Some(seed) => self.rng = StdRng::seed_from_u64(seed),
+                    None => {
+                        self.log_print("Error! seed must be an integer of 64 bits\n".to_string());
+                        self.stack.push(Type::Error("invalid-seed".to_string()));
+                    }
+                }
+            }
+
+            // Uniform random number in [0, 1)
+            "random" => {
+                let value = self.random_unit();
+                self.stack.push(Type::Number(value));
+            }
+
+            // Random integer from low to high inclusive
+            "random-int" => {
+                let high = self.pop_stack().get_number().to_f64().floor() as isize;
+                let low = self.pop_stack().get_number().to_f64().ceil() as isize;
+                if low > high {
+                    self.log_print("Error! range of random-int is empty\n".to_string());
+                    self.stack.push(Type::Error("invalid-range".to_string()));
+                    return;
+                }
+                let value = self.rng.gen_range(low..=high);
+                self.stack.push(Type::Number(Fraction::new(value as f64)));
+            }
+
+            // Normally distributed random number of the mean and standard deviation
+            "random-normal" => {
+                let sd = self.pop_stack().get_number().to_f64();
+                let mean = self.pop_stack().get_number().to_f64();
+                match distribution::Distribution::new("normal", &[mean, sd]) {
+                    Some(normal) => {
+                        let value = normal.sample(&mut self.rng);
+                        self.stack.push(Type::Number(Fraction::new(value)));
+                    }
+                    None => {
+                        self.log_print("Error! standard deviation must be positive\n".to_string());
+                        self.stack
+                            .push(Type::Error("invalid-parameters".to_string()));
+                    }
+                }
+            }
+
+            // Sample of the count from list without replacement
+            "sample" => {
+                let count = self.pop_stack().get_number().to_f64();
+                let list = self.pop_stack().get_list();
+                if count < 0.0 || count as usize > list.len() {
+                    self.log_print("Error! sample is larger than the list\n".to_string());
+                    self.stack.push(Type::Error("invalid-size".to_string()));
+                    return;
+                }
+                let sample = list
+                    .choose_multiple(&mut self.rng, count as usize)
+                    .cloned()
+                    .collect();
+                self.stack.push(Type::List(sample));
+            }
+
+            // Get random value from list with probability proportional to the weight
+            "weighted-choice" => {
+                let weights = self.pop_list_f64();
+                let list = self.pop_stack().get_list();
+                if weights.len() != list.len() {
+                    self.log_print("Error! lengths of list and weights differ\n".to_string());
+                    self.stack.push(Type::Error("length-mismatch".to_string()));
+                    return;
+                }
+                match WeightedIndex::new(&weights) {
+                    Ok(index) => {
+                        let i = index.sample(&mut self.rng);
+                        self.stack.push(list[i].clone());
+                    }
+                    Err(err) => {
+                        self.log_print(format!("Error! {err}\n"));
+                        self.stack.push(Type::Error("invalid-weights".to_string()));
+                    }
+                }
+            }
+
+            // Matrix of uniform random numbers in [0, 1)
+            "random-matrix" => {
+                let cols = self.pop_stack().get_number().to_f64().max(0.0) as usize;
+                let rows = self.pop_stack().get_number().to_f64().max(0.0) as usize;
+                let data = (0..rows * cols).map(|_| self.random_unit()).collect();
+                self.stack.push(Type::Matrix(data, (rows, cols)));
+            }
+
             // Commands of string processing
 
             // Repeat string a number of times
@@ -876,16 +1186,25 @@ impl Executor {
                 }));
             }
 
-            // Generate a string by concat list
+            // Generate a string by concat list, or inner join of two tables on the key column
             "join" => {
                 let key = self.pop_stack().get_string();
-                let mut list = self.pop_stack().get_list();
-                self.stack.push(Type::String(
-                    list.iter_mut()
-                        .map(|x| x.get_string())
-                        .collect::<Vec<String>>()
-                        .join(&key),
-                ))
+                match self.pop_stack() {
+                    Type::Table(right) => {
+                        let Some(left) = self.pop_table() else {
+                            return;
+                        };
+                        let result = left.join(&right, &key);
+                        self.push_table(result, &key);
+                    }
+                    mut list => self.stack.push(Type::String(
+                        list.get_list()
+                            .iter_mut()
+                            .map(|x| x.get_string())
+                            .collect::<Vec<String>>()
+                            .join(&key),
+                    )),
+                }
             }
 
             // Judge is it find in string
@@ -1292,6 +1611,11 @@ impl Executor {
                     Type::List(_) => "list".to_string(),
                     Type::Error(_) => "error".to_string(),
                     Type::Matrix(_, _) => "matrix".to_string(),
+                    Type::Array(_) => "array".to_string(),
+                    Type::Polynomial(_) => "polynomial".to_string(),
+                    Type::Interpolant(_) => "interpolant".to_string(),
+                    Type::Table(_) => "table".to_string(),
+                    Type::Graph(_) => "graph".to_string(),
                 };
 
                 self.stack.push(Type::String(result));
@@ -1307,6 +1631,14 @@ impl Executor {
                     "bool" => self.stack.push(Type::Bool(value.get_bool())),
                     "list" => self.stack.push(Type::List(value.get_list())),
                     "error" => self.stack.push(Type::Error(value.get_string())),
+                    "polynomial" => self.stack.push(Type::Polynomial(value.get_polynomial())),
+                    "array" => match value.get_array() {
+                        Some(array) => self.stack.push(Type::Array(array)),
+                        None => {
+                            self.log_print("Error! list is ragged to be array\n".to_string());
+                            self.stack.push(Type::Error("ragged-array".to_string()));
+                        }
+                    },
                     _ => self.stack.push(value),
                 }
             }
@@ -1447,24 +1779,44 @@ impl Executor {
                 ))
             }
 
-            "transpose" => {
-                let (matrix, (rows, cols)) = self.pop_stack().get_matrix();
-                let matrix = nalgebra::DMatrix::from_row_slice(
-                    rows,
-                    cols,
-                    &matrix.iter().map(|x| x.to_f64()).collect::<Vec<f64>>(),
-                );
-                let transposed_matrix = matrix.transpose();
-
-                let mut transposed_data = Vec::new();
-                for i in 0..transposed_matrix.nrows() {
-                    for j in 0..transposed_matrix.ncols() {
-                        transposed_data.push(Fraction::new(transposed_matrix[(i, j)]));
+            "transpose" => match self.pop_stack() {
+                // Permute axes of the array by the list
+                Type::List(axes) => {
+                    let axes: Vec<usize> = axes
+                        .iter()
+                        .map(|x| x.clone().get_number().to_f64() as usize)
+                        .collect();
+                    match self.pop_stack().get_array().and_then(|x| x.permute(&axes)) {
+                        Some(array) => self.stack.push(Type::Array(array)),
+                        None => {
+                            self.log_print("Error! axes are not a permutation\n".to_string());
+                            self.stack.push(Type::Error("invalid-axes".to_string()));
+                        }
                     }
                 }
 
-                self.stack.push(Type::Matrix(transposed_data, (cols, rows)))
-            }
+                // Reverse axes of the array
+                Type::Array(array) => self.stack.push(Type::Array(array.transpose())),
+
+                mut matrix => {
+                    let (matrix, (rows, cols)) = matrix.get_matrix();
+                    let matrix = nalgebra::DMatrix::from_row_slice(
+                        rows,
+                        cols,
+                        &matrix.iter().map(|x| x.to_f64()).collect::<Vec<f64>>(),
+                    );
+                    let transposed_matrix = matrix.transpose();
+
+                    let mut transposed_data = Vec::new();
+                    for i in 0..transposed_matrix.nrows() {
+                        for j in 0..transposed_matrix.ncols() {
+                            transposed_data.push(Fraction::new(transposed_matrix[(i, j)]));
+                        }
+                    }
+
+                    self.stack.push(Type::Matrix(transposed_data, (cols, rows)))
+                }
+            },
 
             "inverse" => {
                 let (matrix, (rows, cols)) = self.pop_stack().get_matrix();
@@ -1546,60 +1898,2551 @@ impl Executor {
                 ));
             }
 
-            "graph" => {
-                let (data, (row, col)) = self.pop_stack().get_matrix();
-                let adjacency_matrix = nalgebra::DMatrix::<f64>::from_row_slice(
-                    row,
-                    col,
-                    &data.iter().map(|x| x.to_f64()).collect::<Vec<f64>>(),
-                );
-
-                let mut graph = Graph::<f64, ()>::new();
-                let mut node_indices = Vec::new();
+            // Commands of N-dimensional array
 
-                for value in &data.clone() {
-                    let node_index = graph.add_node(value.to_f64());
-                    node_indices.push(node_index);
+            // Convert nested list or matrix to array
+            "array" => match self.pop_stack().get_array() {
+                Some(array) => self.stack.push(Type::Array(array)),
+                None => {
+                    self.log_print("Error! list is ragged to be array\n".to_string());
+                    self.stack.push(Type::Error("ragged-array".to_string()));
                 }
-                for (i, row) in adjacency_matrix.row_iter().enumerate() {
-                    for (j, &value) in row.iter().enumerate() {
-                        if value != 0.0 {
-                            graph.add_edge(node_indices[i], node_indices[j], ());
-                        }
-                    }
-                }
-                let dot = format!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
-                self.stack.push(Type::String(dot))
+            },
+
+            // Get length of each axis
+            "shape" => {
+                let shape = match self.pop_stack() {
+                    Type::Matrix(_, (rows, cols)) => vec![rows, cols],
+                    mut value @ (Type::Array(_) | Type::List(_)) => match value.get_array() {
+                        Some(array) => array.shape().to_vec(),
+                        None => vec![value.get_list().len()],
+                    },
+                    _ => vec![],
+                };
+                self.stack.push(Type::List(
+                    shape
+                        .iter()
+                        .map(|x| Type::Number(Fraction::new(*x as f64)))
+                        .collect(),
+                ));
             }
 
-            "bar-chart" => {
-                let data: Vec<f64> = self
+            // Change shape of array keeping elements
+            "reshape" => {
+                let shape: Vec<isize> = self
                     .pop_stack()
                     .get_list()
                     .iter_mut()
-                    .map(|x| x.get_number().to_f64())
+                    .map(|x| x.get_number().to_f64() as isize)
                     .collect();
-                let mut figure = Figure::new();
-                figure.axes2d().boxes(1..=data.len(), data, &[]);
-                figure.set_title("Bar Chart - NumStack");
-                figure.show().unwrap();
+                match self.pop_stack().get_array().and_then(|x| x.reshape(&shape)) {
+                    Some(array) => self.stack.push(Type::Array(array)),
+                    None => {
+                        self.log_print("Error! shape does not match the array\n".to_string());
+                        self.stack.push(Type::Error("shape-mismatch".to_string()));
+                    }
+                }
             }
 
-            "line-chart" => {
-                let data: Vec<f64> = self
+            // Select part of array by specification like (0, 1:3, ::-1)
+            "slice" => {
+                let spec = self.pop_stack().get_string();
+                match self.pop_stack().get_array().and_then(|x| x.slice(&spec)) {
+                    Some(array) if array.ndim() == 0 => {
+                        self.stack.push(Type::Number(array.elements()[0]))
+                    }
+                    Some(array) => self.stack.push(Type::Array(array)),
+                    None => {
+                        self.log_print(format!("Error! invalid slicing \"{spec}\"\n"));
+                        self.stack.push(Type::Error("invalid-slice".to_string()));
+                    }
+                }
+            }
+
+            // Reduce array along the axis
+            "sum-axis" | "prod-axis" | "mean-axis" | "max-axis" | "min-axis" => {
+                let axis = self.pop_stack().get_number().to_f64() as usize;
+                let fold = |lane: &[Fraction]| match command.as_str() {
+                    "sum-axis" => lane.iter().fold(Fraction::new(0.0), |acc, x| acc + *x),
+                    "prod-axis" => lane.iter().fold(Fraction::new(1.0), |acc, x| acc * *x),
+                    "mean-axis" => {
+                        lane.iter().fold(Fraction::new(0.0), |acc, x| acc + *x)
+                            / Fraction::new(lane.len() as f64)
+                    }
+                    "max-axis" => *lane
+                        .iter()
+                        .max_by(|a, b| a.to_f64().total_cmp(&b.to_f64()))
+                        .unwrap(),
+                    _ => *lane
+                        .iter()
+                        .min_by(|a, b| a.to_f64().total_cmp(&b.to_f64()))
+                        .unwrap(),
+                };
+                match self
                     .pop_stack()
-                    .get_list()
-                    .iter_mut()
-                    .map(|x| x.get_number().to_f64())
+                    .get_array()
+                    .and_then(|x| x.reduce(axis, fold))
+                {
+                    Some(array) if array.ndim() == 0 => {
+                        self.stack.push(Type::Number(array.elements()[0]))
+                    }
+                    Some(array) => self.stack.push(Type::Array(array)),
+                    None => {
+                        self.log_print("Error! axis is out of range\n".to_string());
+                        self.stack.push(Type::Error("invalid-axes".to_string()));
+                    }
+                }
+            }
+
+            // Commands of polynomial
+
+            // Make polynomial from coefficients of the highest power first
+            "poly" => {
+                let poly = self.pop_stack().get_polynomial();
+                self.stack.push(Type::Polynomial(poly));
+            }
+
+            // Value of polynomial at the point or every element of list, matrix and array
+            "poly-eval" => {
+                let value = self.pop_stack();
+                let poly = self.pop_stack().get_polynomial();
+                self.stack.push(value.map_exact(&|x| poly.eval(x)));
+            }
+
+            // Derivative of polynomial
+            "poly-diff" => {
+                let poly = self.pop_stack().get_polynomial();
+                self.stack.push(Type::Polynomial(poly.derivative()));
+            }
+
+            // Antiderivative of polynomial whose constant term is zero
+            "poly-integrate" => {
+                let poly = self.pop_stack().get_polynomial();
+                self.stack.push(Type::Polynomial(poly.integral()));
+            }
+
+            // Monic greatest common divisor of polynomials
+            "poly-gcd" => {
+                let b = self.pop_stack().get_polynomial();
+                let a = self.pop_stack().get_polynomial();
+                self.stack.push(Type::Polynomial(a.gcd(&b)));
+            }
+
+            // Roots of polynomial, exact if rational and complex ones as [real imaginary]
+            "poly-roots" => {
+                let poly = self.pop_stack().get_polynomial();
+                if poly.is_zero() {
+                    self.log_print("Error! every number is root of zero polynomial\n".to_string());
+                    self.stack.push(Type::Error("zero-polynomial".to_string()));
+                    return;
+                }
+
+                let (exact, rest) = poly.roots();
+                let mut roots: Vec<(f64, f64, Type)> = exact
+                    .into_iter()
+                    .map(|x| (x.to_f64(), 0.0, Type::Number(x)))
                     .collect();
-                let mut figure = Figure::new();
-                figure.axes2d().lines(1..=data.len(), data, &[]);
-                figure.set_title("Line Chart - NumStack");
-                figure.show().unwrap();
+                for (re, im) in rest {
+                    let root = if im == 0.0 {
+                        Type::Number(Fraction::new(re))
+                    } else {
+                        Type::List(vec![
+                            Type::Number(Fraction::new(re)),
+                            Type::Number(Fraction::new(im)),
+                        ])
+                    };
+                    roots.push((re, im, root));
+                }
+                roots.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+                self.stack
+                    .push(Type::List(roots.into_iter().map(|x| x.2).collect()));
             }
 
-            // If it is not recognized as a command, use it as a string.
-            _ => self.stack.push(Type::String(command)),
+            // Commands of interpolation
+            // The query is a point or list of points, or a string like (fn) for the interpolant
+
+            // Piecewise linear interpolation of the samples
+            "interp-linear" => {
+                let query = self.pop_stack();
+                match self.pop_samples(2, true) {
+                    Ok((xs, ys)) => {
+                        let interpolant = Interpolant::linear(&xs, &ys);
+                        self.stack.push(match query {
+                            Type::String(_) => Type::Interpolant(interpolant),
+                            query => query.map_exact(&|x| interpolant.eval(x)),
+                        });
+                    }
+                    Err(err) => self.stack.push(err),
+                }
+            }
+
+            // Polynomial interpolation by Lagrange's formula
+            "interp-lagrange" => {
+                let query = self.pop_stack();
+                match self.pop_samples(1, false) {
+                    Ok((xs, ys)) => self.stack.push(match query {
+                        Type::String(_) => {
+                            Type::Polynomial(interpolate::lagrange_polynomial(&xs, &ys))
+                        }
+                        query => query.map_exact(&|x| interpolate::lagrange(&xs, &ys, x)),
+                    }),
+                    Err(err) => self.stack.push(err),
+                }
+            }
+
+            // Polynomial interpolation by Newton's divided differences
+            "interp-newton" => {
+                let query = self.pop_stack();
+                match self.pop_samples(1, false) {
+                    Ok((xs, ys)) => {
+                        let coefficients = interpolate::divided_differences(&xs, &ys);
+                        self.stack.push(match query {
+                            Type::String(_) => {
+                                Type::Polynomial(interpolate::newton_polynomial(&xs, &coefficients))
+                            }
+                            query => {
+                                query.map_exact(&|x| interpolate::newton(&xs, &coefficients, x))
+                            }
+                        });
+                    }
+                    Err(err) => self.stack.push(err),
+                }
+            }
+
+            // Cubic spline interpolation, natural by (natural) or clamped by [start-slope end-slope]
+            "cubic-spline" => {
+                let slopes = match self.pop_stack() {
+                    Type::List(slopes) if slopes.len() == 2 => Some((
+                        slopes[0].clone().get_number(),
+                        slopes[1].clone().get_number(),
+                    )),
+                    _ => None,
+                };
+                let query = self.pop_stack();
+                match self.pop_samples(2, true) {
+                    Ok((xs, ys)) => {
+                        let interpolant = Interpolant::cubic_spline(&xs, &ys, slopes);
+                        self.stack.push(match query {
+                            Type::String(_) => Type::Interpolant(interpolant),
+                            query => query.map_exact(&|x| interpolant.eval(x)),
+                        });
+                    }
+                    Err(err) => self.stack.push(err),
+                }
+            }
+
+            // Evaluate interpolant or polynomial at the query
+            "interp-eval" => {
+                let query = self.pop_stack();
+                let result = match self.pop_stack() {
+                    Type::Interpolant(interpolant) => query.map_exact(&|x| interpolant.eval(x)),
+                    mut other => {
+                        let poly = other.get_polynomial();
+                        query.map_exact(&|x| poly.eval(x))
+                    }
+                };
+                self.stack.push(result);
+            }
+
+            // Commands of table
+
+            // Make table from list of column names and list of columns
+            "table" => {
+                let mut columns = self.pop_stack().get_list();
+                let columns: Vec<Vec<Type>> = columns.iter_mut().map(|x| x.get_list()).collect();
+                let mut names = self.pop_stack().get_list();
+                let names: Vec<String> = names.iter_mut().map(|x| x.get_string()).collect();
+                match Table::new(names, columns) {
+                    Ok(table) => self.stack.push(Type::Table(table)),
+                    Err(e) => {
+                        self.log_print(format!("Error! {e}\n"));
+                        self.stack.push(Type::Error("shape-mismatch".to_string()));
+                    }
+                }
+            }
+
+            // Read table from CSV file whose first line is header
+            "read-csv" => {
+                let name = Path::new(&self.pop_stack().get_string()).to_owned();
+                let text = match get_file_contents(&name) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        self.log_print(format!("Error! {e}\n"));
+                        self.stack.push(Type::Error("read-file".to_string()));
+                        return;
+                    }
+                };
+                match Table::from_csv(&text) {
+                    Ok(table) => self.stack.push(Type::Table(table)),
+                    Err(e) => {
+                        self.log_print(format!("Error! {e}\n"));
+                        self.stack.push(Type::Error("invalid-csv".to_string()));
+                    }
+                }
+            }
+
+            // Write table to CSV file
+            "write-csv" => {
+                let name = self.pop_stack().get_string();
+                let Some(table) = self.pop_table() else {
+                    return;
+                };
+                let mut file = match File::create(Path::new(&name)) {
+                    Ok(file) => file,
+                    Err(e) => {
+                        self.log_print(format!("Error! {e}\n"));
+                        self.stack.push(Type::Error("create-file".to_string()));
+                        return;
+                    }
+                };
+                if let Err(e) = file.write_all(table.to_csv().as_bytes()) {
+                    self.log_print(format!("Error! {e}\n"));
+                    self.stack.push(Type::Error("write-file".to_string()));
+                }
+            }
+
+            // Get names of columns
+            "columns" => {
+                if let Some(table) = self.pop_table() {
+                    let names = table.names().iter().cloned().map(Type::String).collect();
+                    self.stack.push(Type::List(names));
+                }
+            }
+
+            // Get column of the name as list
+            "column" => {
+                let name = self.pop_stack().get_string();
+                let Some(table) = self.pop_table() else {
+                    return;
+                };
+                let column = table
+                    .column(&name)
+                    .map(|column| (0..table.rows()).map(|i| column.get(i)).collect());
+                match column {
+                    Some(column) => self.stack.push(Type::List(column)),
+                    None => self.push_table(None, &name),
+                }
+            }
+
+            // Select columns of the list of names
+            "select" => {
+                let mut names = self.pop_stack().get_list();
+                let names: Vec<String> = names.iter_mut().map(|x| x.get_string()).collect();
+                if let Some(table) = self.pop_table() {
+                    let result = table.select(&names);
+                    self.push_table(result, &names.join(" "));
+                }
+            }
+
+            // Filter rows by the code, where columns are bound to variables of their names
+            "filter-rows" => {
+                let code = self.pop_stack().get_string();
+                let Some(table) = self.pop_table() else {
+                    return;
+                };
+
+                let mut rows = Vec::new();
+                for i in 0..table.rows() {
+                    for name in table.names() {
+                        let value = table.column(name).map(|column| column.get(i));
+                        if let Some(value) = value {
+                            self.memory
+                                .entry(name.clone())
+                                .and_modify(|x| *x = value.clone())
+                                .or_insert(value);
+                        }
+                    }
+                    self.evaluate_program(code.clone());
+                    if self.pop_stack().get_bool() {
+                        rows.push(i);
+                    }
+                }
+                self.stack.push(Type::Table(table.take(&rows)));
+            }
+
+            // Sort rows by the column, in descending order by like (name desc)
+            "sort-by" => {
+                let key = self.pop_stack().get_string();
+                let (name, descending) = match key.split_whitespace().collect::<Vec<&str>>()[..] {
+                    [name, "desc"] => (name.to_string(), true),
+                    _ => (key.trim().to_string(), false),
+                };
+                if let Some(table) = self.pop_table() {
+                    let result = table.sort_by(&name, descending);
+                    self.push_table(result, &name);
+                }
+            }
+
+            // Aggregate numeric columns of each group of the key by count, sum, mean,
+            // median, min, max, variance or stdev
+            "group-by" => {
+                let function = self.pop_stack().get_string();
+                let key = self.pop_stack().get_string();
+                if let Some(table) = self.pop_table() {
+                    let result = table.group_by(&key, &function);
+                    self.push_table(result, &format!("{key} or function {function}"));
+                }
+            }
+
+            // Convert numeric columns to matrix
+            "to-matrix" => {
+                if let Some(table) = self.pop_table() {
+                    let (data, size) = table.to_matrix();
+                    self.stack.push(Type::Matrix(data, size));
+                }
+            }
+
+            // Commands of statistics
+            // Each is applied to a list, or to each column of a matrix
+
+            // Statistic of the data, variance and stdev are of sample,
+            // pvariance and pstdev are of population
+            "sum" | "mean" | "median" | "mode" | "variance" | "pvariance" | "stdev" | "pstdev"
+            | "iqr" | "skewness" | "kurtosis" => {
+                let statistic: fn(&[Fraction]) -> Option<Fraction> = match command.as_str() {
+                    "sum" => |x| Some(statistics::sum(x)),
+                    "mean" => statistics::mean,
+                    "median" => statistics::median,
+                    "mode" => statistics::mode,
+                    "variance" => |x| statistics::variance(x, true),
+                    "pvariance" => |x| statistics::variance(x, false),
+                    "stdev" => |x| statistics::stdev(x, true).map(Fraction::new),
+                    "pstdev" => |x| statistics::stdev(x, false).map(Fraction::new),
+                    "iqr" => statistics::iqr,
+                    "skewness" => |x| statistics::skewness(x).map(Fraction::new),
+                    _ => statistics::kurtosis,
+                };
+                let data = self.pop_stack();
+                let result = self.statistic(data, &statistic);
+                self.stack.push(result);
+            }
+
+            // Quantile of probability from 0 to 1, or percentile from 0 to 100, also for list of them
+            "quantile" | "percentile" => {
+                let scale = Fraction::new(if command == "percentile" { 100.0 } else { 1.0 });
+                let mut probability = self.pop_stack();
+                let data = self.pop_stack();
+
+                let probabilities: Vec<Fraction> = probability
+                    .get_list()
+                    .iter_mut()
+                    .map(|x| x.get_number() / scale)
+                    .collect();
+                if probabilities
+                    .iter()
+                    .any(|p| !(0.0..=1.0).contains(&p.to_f64()))
+                {
+                    self.log_print(format!("Error! {command} is out of range\n"));
+                    self.stack.push(Type::Error("invalid-quantile".to_string()));
+                    return;
+                }
+                let mut results: Vec<Type> = probabilities
+                    .iter()
+                    .map(|p| self.statistic(data.clone(), &|x| statistics::quantile(x, *p)))
+                    .collect();
+                match probability {
+                    Type::List(_) => self.stack.push(Type::List(results)),
+                    _ => self.stack.push(results.remove(0)),
+                }
+            }
+
+            // Covariance of sample or correlation coefficient of two lists,
+            // or matrix of them between columns of a matrix
+            "covariance" | "correlation" => {
+                let statistic: fn(&[Fraction], &[Fraction]) -> Option<Fraction> =
+                    if command == "covariance" {
+                        |x, y| statistics::covariance(x, y, true)
+                    } else {
+                        |x, y| statistics::correlation(x, y).map(Fraction::new)
+                    };
+                let result = match self.pop_stack() {
+                    Type::Matrix(data, (rows, cols)) => {
+                        let columns: Vec<Vec<Fraction>> = (0..cols)
+                            .map(|j| (0..rows).map(|i| data[i * cols + j]).collect())
+                            .collect();
+                        let mut matrix = Vec::new();
+                        for x in &columns {
+                            for y in &columns {
+                                matrix.push(statistic(x, y));
+                            }
+                        }
+                        matrix
+                            .into_iter()
+                            .collect::<Option<Vec<Fraction>>>()
+                            .map(|matrix| Type::Matrix(matrix, (cols, cols)))
+                    }
+                    mut y => {
+                        let to_fractions = |list: Vec<Type>| -> Vec<Fraction> {
+                            list.into_iter().map(|mut x| x.get_number()).collect()
+                        };
+                        let y = to_fractions(y.get_list());
+                        let x = to_fractions(self.pop_stack().get_list());
+                        statistic(&x, &y).map(Type::Number)
+                    }
+                };
+                match result {
+                    Some(result) => self.stack.push(result),
+                    None => {
+                        self.log_print(format!("Error! {command} is undefined for the data\n"));
+                        self.stack
+                            .push(Type::Error("undefined-statistic".to_string()));
+                    }
+                }
+            }
+
+            // Commands of probability distribution
+            // Normal, uniform, exponential, gamma, beta, t, chi-squared, f, binomial,
+            // poisson, geometric and hypergeometric have commands like normal-pdf,
+            // whose parameters are given as a list like [mean sd]
+
+            // Bin the data into [[edges] [counts]], bins are given by count, list of edges,
+            // or rule of (sturges), (freedman-diaconis) or (width 0.5)
+            "histogram" | "cumulative-frequency" => match self.pop_histogram() {
+                Ok((edges, counts)) => {
+                    let counts = counts.into_iter().scan(0, |total, count| {
+                        *total = if command == "histogram" {
+                            count
+                        } else {
+                            *total + count
+                        };
+                        Some(Type::Number(Fraction::new(*total as f64)))
+                    });
+                    self.stack.push(Type::List(vec![
+                        Type::List(edges.into_iter().map(Type::Number).collect()),
+                        Type::List(counts.collect()),
+                    ]));
+                }
+                Err(error) => self.stack.push(error),
+            },
+
+            // Commands of hypothesis test
+            // Each results [statistic p-value degrees-of-freedom], whose degrees are
+            // a list for F distribution, or empty list if there are none
+
+            // One-sample t-test whether mean of the data is the value
+            "t-test" => {
+                let mu = self.pop_stack().get_number().to_f64();
+                let data = self.pop_list_f64();
+                self.push_test(&command, hypothesis::t_one(&data, mu));
+            }
+
+            // Paired t-test or Welch's t-test of two samples
+            "t-test-paired" | "t-test-welch" => {
+                let y = self.pop_list_f64();
+                let x = self.pop_list_f64();
+                let test = if command == "t-test-paired" {
+                    hypothesis::t_paired(&x, &y)
+                } else {
+                    hypothesis::t_welch(&x, &y)
+                };
+                self.push_test(&command, test);
+            }
+
+            // Z-test whether mean of the data is the value, by known standard deviation
+            "z-test" => {
+                let sigma = self.pop_stack().get_number().to_f64();
+                let mu = self.pop_stack().get_number().to_f64();
+                let data = self.pop_list_f64();
+                self.push_test(&command, hypothesis::z_test(&data, mu, sigma));
+            }
+
+            // Chi-squared test of independence on contingency table of matrix,
+            // or goodness-of-fit of observed counts to expected ones
+            "chi-squared-test" => {
+                let test = match self.pop_stack() {
+                    Type::Matrix(table, size) => {
+                        let table: Vec<f64> = table.iter().map(|x| x.to_f64()).collect();
+                        hypothesis::chi_squared_independence(&table, size)
+                    }
+                    mut expected => {
+                        let expected: Vec<f64> = expected
+                            .get_list()
+                            .iter_mut()
+                            .map(|x| x.get_number().to_f64())
+                            .collect();
+                        let observed = self.pop_list_f64();
+                        hypothesis::chi_squared_fit(&observed, &expected)
+                    }
+                };
+                self.push_test(&command, test);
+            }
+
+            // One-way ANOVA of list of groups
+            "anova" => {
+                let groups: Vec<Vec<f64>> = self
+                    .pop_stack()
+                    .get_list()
+                    .iter_mut()
+                    .map(|group| {
+                        group
+                            .get_list()
+                            .iter_mut()
+                            .map(|x| x.get_number().to_f64())
+                            .collect()
+                    })
+                    .collect();
+                self.push_test(&command, hypothesis::anova(&groups));
+            }
+
+            // Mann–Whitney U test of two samples
+            "mann-whitney" => {
+                let y = self.pop_list_f64();
+                let x = self.pop_list_f64();
+                self.push_test(&command, hypothesis::mann_whitney(&x, &y));
+            }
+
+            // Kolmogorov–Smirnov test of two samples,
+            // or of the data against distribution like [0 1] (normal)
+            "ks-test" => {
+                let test = match self.pop_stack() {
+                    Type::String(name) => {
+                        let parameters = self.pop_list_f64();
+                        let data = self.pop_list_f64();
+                        match distribution::Distribution::new(&name, &parameters) {
+                            Some(distribution) => {
+                                hypothesis::ks_one(&data, &|x| distribution.cdf(x))
+                            }
+                            None => {
+                                self.log_print(format!(
+                                    "Error! distribution {name} or its parameters are invalid\n"
+                                ));
+                                self.stack
+                                    .push(Type::Error("invalid-parameters".to_string()));
+                                return;
+                            }
+                        }
+                    }
+                    mut y => {
+                        let y: Vec<f64> = y
+                            .get_list()
+                            .iter_mut()
+                            .map(|x| x.get_number().to_f64())
+                            .collect();
+                        let x = self.pop_list_f64();
+                        hypothesis::ks_two(&x, &y)
+                    }
+                };
+                self.push_test(&command, test);
+            }
+
+            // Test of Pearson's correlation or Spearman's rank correlation,
+            // whose statistic is the coefficient
+            "pearson-test" | "spearman-test" => {
+                let y = self.pop_list_f64();
+                let x = self.pop_list_f64();
+                let test = if command == "pearson-test" {
+                    hypothesis::pearson(&x, &y)
+                } else {
+                    hypothesis::spearman(&x, &y)
+                };
+                self.push_test(&command, test);
+            }
+
+            // Commands of regression
+
+            // Least squares line of the data as [slope intercept r²]
+            "linear-regression" => match self.pop_data_f64() {
+                Ok((xs, ys)) => match regression::linear(&xs, &ys) {
+                    Some((slope, intercept, r2)) => self.stack.push(Type::List(vec![
+                        Type::Number(Fraction::new(slope)),
+                        Type::Number(Fraction::new(intercept)),
+                        Type::Number(Fraction::new(r2)),
+                    ])),
+                    None => {
+                        self.log_print("Error! x of the data must not be constant\n".to_string());
+                        self.stack.push(Type::Error("singular-data".to_string()));
+                    }
+                },
+                Err(err) => self.stack.push(err),
+            },
+
+            // Least squares polynomial of the degree
+            "polyfit" => {
+                let degree = self.pop_stack().get_number().to_f64().max(0.0) as usize;
+                match self.pop_data_f64() {
+                    Ok((xs, _)) if xs.len() <= degree => {
+                        self.log_print(format!(
+                            "Error! polynomial of degree {degree} needs more than {degree} samples\n"
+                        ));
+                        self.stack.push(Type::Error("too-few-samples".to_string()));
+                    }
+                    Ok((xs, ys)) => match regression::polyfit(&xs, &ys, degree) {
+                        Some(coefficients) => self.stack.push(Type::Polynomial(Polynomial::new(
+                            coefficients.iter().map(|x| Fraction::new(*x)).collect(),
+                        ))),
+                        None => {
+                            self.log_print("Error! least squares cannot be solved\n".to_string());
+                            self.stack.push(Type::Error("singular-data".to_string()));
+                        }
+                    },
+                    Err(err) => self.stack.push(err),
+                }
+            }
+
+            // Multiple linear regression on the design matrix whose rows are samples,
+            // returns [[coefficients] r²]
+            "multi-regression" => {
+                let ys = self.pop_list_f64();
+                let (data, (rows, cols)) = self.pop_stack().get_matrix();
+                if rows == 0 || cols == 0 {
+                    self.log_print("Error! design matrix is empty\n".to_string());
+                    self.stack.push(Type::Error("too-few-samples".to_string()));
+                    return;
+                }
+                if rows != ys.len() {
+                    self.log_print(
+                        "Error! rows of design matrix and data are different\n".to_string(),
+                    );
+                    self.stack.push(Type::Error("shape-mismatch".to_string()));
+                    return;
+                }
+                let design = nalgebra::DMatrix::from_row_slice(
+                    rows,
+                    cols,
+                    &data.iter().map(|x| x.to_f64()).collect::<Vec<f64>>(),
+                );
+                match regression::least_squares(design.clone(), &ys) {
+                    Some(coefficients) => {
+                        let fitted = design * nalgebra::DVector::from_column_slice(&coefficients);
+                        let r2 = regression::r_squared(&ys, fitted.as_slice());
+                        self.stack.push(Type::List(vec![
+                            Type::List(
+                                coefficients
+                                    .iter()
+                                    .map(|x| Type::Number(Fraction::new(*x)))
+                                    .collect(),
+                            ),
+                            Type::Number(Fraction::new(r2)),
+                        ]));
+                    }
+                    None => {
+                        self.log_print("Error! least squares cannot be solved\n".to_string());
+                        self.stack.push(Type::Error("singular-data".to_string()));
+                    }
+                }
+            }
+
+            // Fit model by Levenberg-Marquardt method from initial parameters,
+            // the first variable is x and the rest are parameters,
+            // returns [[parameters] [uncertainties] residual-sum-of-squares iterations]
+            "curve-fit" => {
+                let code = self.pop_stack().get_string();
+                let vars = self.pop_stack().get_string();
+                let vars: Vec<String> = vars.split_whitespace().map(String::from).collect();
+                let start = self.pop_list_f64();
+                let (xs, ys) = match self.pop_data_f64() {
+                    Ok(data) => data,
+                    Err(err) => {
+                        self.stack.push(err);
+                        return;
+                    }
+                };
+                if vars.len() != start.len() + 1 {
+                    self.log_print("Error! variables must be x and each parameter\n".to_string());
+                    self.stack.push(Type::Error("length-mismatch".to_string()));
+                    return;
+                }
+
+                let mut model = |parameters: &[f64]| -> Vec<f64> {
+                    xs.iter()
+                        .map(|x| {
+                            let mut args = vec![*x];
+                            args.extend_from_slice(parameters);
+                            self.call_function(&vars, &code, &args)
+                                .first()
+                                .copied()
+                                .unwrap_or(f64::NAN)
+                        })
+                        .collect()
+                };
+                match regression::levenberg_marquardt(&mut model, &ys, &start) {
+                    Some(fit) => {
+                        let list = |values: &[f64]| {
+                            Type::List(
+                                values
+                                    .iter()
+                                    .map(|x| Type::Number(Fraction::new(*x)))
+                                    .collect(),
+                            )
+                        };
+                        self.stack.push(Type::List(vec![
+                            list(&fit.parameters),
+                            list(&fit.uncertainties),
+                            Type::Number(Fraction::new(fit.residual)),
+                            Type::Number(Fraction::new(fit.iterations as f64)),
+                        ]));
+                    }
+                    None => {
+                        self.log_print("Error! fitting does not converge\n".to_string());
+                        self.stack.push(Type::Error("no-convergence".to_string()));
+                    }
+                }
+            }
+
+            // Commands of Fourier analysis
+            // Complex numbers are represented as [real imaginary]
+
+            // Discrete Fourier transform by fast algorithm of any length
+            "fft" => {
+                let signal = self.pop_complex_list();
+                self.push_complex_list(fourier::fft(&signal));
+            }
+
+            // Inverse discrete Fourier transform
+            "ifft" => {
+                let spectrum = self.pop_complex_list();
+                self.push_complex_list(fourier::ifft(&spectrum));
+            }
+
+            // Transform of real signal up to the Nyquist frequency
+            "rfft" => {
+                let signal = self.pop_list_f64();
+                self.push_complex_list(fourier::rfft(&signal));
+            }
+
+            // Power of real signal at each frequency up to the Nyquist one, |X|² / N
+            "power-spectrum" => {
+                let signal = self.pop_list_f64();
+                let n = signal.len() as f64;
+                let power = fourier::rfft(&signal)
+                    .iter()
+                    .map(|z| Type::Number(Fraction::new(z.norm_sqr() / n)))
+                    .collect();
+                self.stack.push(Type::List(power));
+            }
+
+            // Linear convolution by (direct) definition or (fft)
+            "convolve" | "correlate" => {
+                let method = self.pop_stack().get_string();
+                let mut b = self.pop_stack().get_list();
+                let a = self.pop_stack().get_list();
+                if command == "correlate" {
+                    // Correlation is convolution with the reversed signal
+                    b.reverse();
+                }
+                let result = match method.as_str() {
+                    "direct" => {
+                        let to_fractions = |list: Vec<Type>| -> Vec<Fraction> {
+                            list.into_iter().map(|mut x| x.get_number()).collect()
+                        };
+                        fourier::convolve_direct(&to_fractions(a), &to_fractions(b))
+                    }
+                    "fft" => {
+                        let to_f64 = |list: Vec<Type>| -> Vec<f64> {
+                            list.into_iter()
+                                .map(|mut x| x.get_number().to_f64())
+                                .collect()
+                        };
+                        fourier::convolve(&to_f64(a), &to_f64(b))
+                            .into_iter()
+                            .map(Fraction::new)
+                            .collect()
+                    }
+                    _ => {
+                        self.log_print(format!("Error! unknown method \"{method}\"\n"));
+                        self.stack.push(Type::Error("unknown-method".to_string()));
+                        return;
+                    }
+                };
+                self.stack
+                    .push(Type::List(result.into_iter().map(Type::Number).collect()));
+            }
+
+            // Window function of the length by (hann), (hamming) or (blackman)
+            "window" => {
+                let kind = self.pop_stack().get_string();
+                let length = self.pop_stack().get_number().to_f64().max(0.0) as usize;
+                match fourier::window(&kind, length) {
+                    Some(window) => self.stack.push(Type::List(
+                        window
+                            .into_iter()
+                            .map(|x| Type::Number(Fraction::new(x)))
+                            .collect(),
+                    )),
+                    None => {
+                        self.log_print(format!("Error! unknown window \"{kind}\"\n"));
+                        self.stack.push(Type::Error("unknown-method".to_string()));
+                    }
+                }
+            }
+
+            // Commands of linear programming
+
+            // Optimize c·x subject to Ax <= b and x >= 0 by sense (max) or (min),
+            // over integers for ilp-solve, returns [optimum [solution]]
+            "lp-solve" | "ilp-solve" => {
+                let sense = self.pop_stack().get_string();
+                let b: Vec<Fraction> = self
+                    .pop_stack()
+                    .get_list()
+                    .iter_mut()
+                    .map(|x| x.get_number())
+                    .collect();
+                let (data, (rows, cols)) = self.pop_stack().get_matrix();
+                let mut c: Vec<Fraction> = self
+                    .pop_stack()
+                    .get_list()
+                    .iter_mut()
+                    .map(|x| x.get_number())
+                    .collect();
+
+                if cols != c.len() || rows != b.len() {
+                    self.log_print(
+                        "Error! sizes of objective, constraints and bounds are different\n"
+                            .to_string(),
+                    );
+                    self.stack.push(Type::Error("shape-mismatch".to_string()));
+                    return;
+                }
+                let minimize = match sense.as_str() {
+                    "max" => false,
+                    "min" => true,
+                    _ => {
+                        self.log_print(format!(
+                            "Error! unknown sense \"{sense}\", use (max) or (min)\n"
+                        ));
+                        self.stack.push(Type::Error("unknown-sense".to_string()));
+                        return;
+                    }
+                };
+                if minimize {
+                    c = c.iter().map(|x| Fraction::new(0.0) - *x).collect();
+                }
+                let a: Vec<Vec<Fraction>> =
+                    data.chunks(cols.max(1)).map(|row| row.to_vec()).collect();
+
+                let outcome = if command == "lp-solve" {
+                    Some(linprog::simplex(&c, &a, &b))
+                } else {
+                    linprog::branch_and_bound(&c, &a, &b)
+                };
+                match outcome {
+                    Some(linprog::Outcome::Optimal(optimum, solution)) => {
+                        let optimum = if minimize {
+                            Fraction::new(0.0) - optimum
+                        } else {
+                            optimum
+                        };
+                        self.stack.push(Type::List(vec![
+                            Type::Number(optimum),
+                            Type::List(solution.into_iter().map(Type::Number).collect()),
+                        ]));
+                    }
+                    Some(linprog::Outcome::Infeasible) => {
+                        self.log_print(
+                            "Error! no solution satisfies the constraints\n".to_string(),
+                        );
+                        self.stack.push(Type::Error("infeasible".to_string()));
+                    }
+                    Some(linprog::Outcome::Unbounded) => {
+                        self.log_print("Error! objective is unbounded\n".to_string());
+                        self.stack.push(Type::Error("unbounded".to_string()));
+                    }
+                    None => {
+                        self.log_print("Error! branch and bound exceeds the limit\n".to_string());
+                        self.stack.push(Type::Error("no-convergence".to_string()));
+                    }
+                }
+            }
+
+            // Commands of calculus
+
+            // Numerical derivative of the order at the point
+            "derivative" => {
+                let code = self.pop_stack().get_string();
+                let vars = [self.pop_stack().get_string()];
+                let order = self.pop_stack().get_number().to_f64() as usize;
+                let point = self.pop_stack().get_number().to_f64();
+
+                let (value, _) =
+                    calculus::derivative(|x| self.call_function(&vars, &code, &[x]), point, order);
+                match value.first() {
+                    Some(value) if value.is_finite() => {
+                        self.stack.push(Type::Number(Fraction::new(*value)))
+                    }
+                    _ => {
+                        self.log_print("Error! function is not differentiable\n".to_string());
+                        self.stack
+                            .push(Type::Error("not-differentiable".to_string()));
+                    }
+                }
+            }
+
+            // Vector of partial derivatives at the point
+            "gradient" => {
+                let code = self.pop_stack().get_string();
+                let vars = self.pop_stack().get_string();
+                let vars: Vec<String> = vars.split_whitespace().map(String::from).collect();
+                let point = self.pop_list_f64();
+
+                let mut gradient = Vec::new();
+                for i in 0..point.len() {
+                    let (value, _) = calculus::derivative(
+                        |x| {
+                            let mut args = point.clone();
+                            args[i] = x;
+                            self.call_function(&vars, &code, &args)
+                        },
+                        point[i],
+                        1,
+                    );
+                    gradient.push(value.first().copied().unwrap_or(f64::NAN));
+                }
+                self.push_matrix_f64(gradient, (point.len(), 1));
+            }
+
+            // Matrix of partial derivatives of the function returning list
+            "jacobian" => {
+                let code = self.pop_stack().get_string();
+                let vars = self.pop_stack().get_string();
+                let vars: Vec<String> = vars.split_whitespace().map(String::from).collect();
+                let point = self.pop_list_f64();
+
+                let mut columns = Vec::new();
+                for i in 0..point.len() {
+                    let (column, _) = calculus::derivative(
+                        |x| {
+                            let mut args = point.clone();
+                            args[i] = x;
+                            self.call_function(&vars, &code, &args)
+                        },
+                        point[i],
+                        1,
+                    );
+                    columns.push(column);
+                }
+
+                let rows = columns.first().map(|x| x.len()).unwrap_or(0);
+                let mut jacobian = Vec::new();
+                for row in 0..rows {
+                    for column in &columns {
+                        jacobian.push(column.get(row).copied().unwrap_or(f64::NAN));
+                    }
+                }
+                self.push_matrix_f64(jacobian, (rows, point.len()));
+            }
+
+            // Matrix of second partial derivatives at the point
+            "hessian" => {
+                let code = self.pop_stack().get_string();
+                let vars = self.pop_stack().get_string();
+                let vars: Vec<String> = vars.split_whitespace().map(String::from).collect();
+                let point = self.pop_list_f64();
+
+                let n = point.len();
+                let mut hessian = vec![0.0; n * n];
+                for i in 0..n {
+                    let (value, _) = calculus::derivative(
+                        |x| {
+                            let mut args = point.clone();
+                            args[i] = x;
+                            self.call_function(&vars, &code, &args)
+                        },
+                        point[i],
+                        2,
+                    );
+                    hessian[i * n + i] = value.first().copied().unwrap_or(f64::NAN);
+
+                    for j in 0..i {
+                        let (value, _) = calculus::mixed_partial(
+                            |args| {
+                                let value = self.call_function(&vars, &code, args);
+                                value.first().copied().unwrap_or(f64::NAN)
+                            },
+                            &point,
+                            i,
+                            j,
+                        );
+                        hessian[i * n + j] = value;
+                        hessian[j * n + i] = value;
+                    }
+                }
+                self.push_matrix_f64(hessian, (n, n));
+            }
+
+            // Definite integral of the function over bounds, (inf) is allowed
+            "integrate" => {
+                let method = self.pop_stack().get_string();
+                let code = self.pop_stack().get_string();
+                let vars = [self.pop_stack().get_string()];
+                let bounds: Vec<f64> = self
+                    .pop_stack()
+                    .get_list()
+                    .iter_mut()
+                    .map(|x| x.get_bound())
+                    .collect();
+
+                let rule: calculus::Rule = match method.as_str() {
+                    "simpson" => calculus::simpson,
+                    "gauss-legendre" => calculus::gauss_legendre,
+                    "gauss-kronrod" => calculus::gauss_kronrod,
+                    _ => {
+                        self.log_print(format!("Error! unknown integration method {method}\n"));
+                        self.stack.push(Type::Error("unknown-method".to_string()));
+                        return;
+                    }
+                };
+
+                let (a, b) = (
+                    bounds.first().copied().unwrap_or(0.0),
+                    bounds.get(1).copied().unwrap_or(0.0),
+                );
+                let mut f = |x: f64| {
+                    let value = self.call_function(&vars, &code, &[x]);
+                    value.first().copied().unwrap_or(f64::NAN)
+                };
+                match calculus::integrate(&mut f, a, b, rule) {
+                    Some((value, error)) if value.is_finite() => self.stack.push(Type::List(vec![
+                        Type::Number(Fraction::new(value)),
+                        Type::Number(Fraction::new(error)),
+                    ])),
+                    _ => {
+                        self.log_print("Error! integration does not converge\n".to_string());
+                        self.stack.push(Type::Error("no-convergence".to_string()));
+                    }
+                }
+            }
+
+            // Integral of sampled values by trapezoidal rule
+            "trapz" => {
+                let x = self.pop_list_f64();
+                let y = self.pop_list_f64();
+                if x.len() == y.len() {
+                    self.stack
+                        .push(Type::Number(Fraction::new(calculus::trapz(&x, &y))));
+                } else {
+                    self.log_print("Error! lists are different length\n".to_string());
+                    self.stack.push(Type::Error("length-mismatch".to_string()));
+                }
+            }
+
+            // Solve initial value problem dy/dt = f(t, y) over [t0 t1 step]
+            "ode-solve" => {
+                let method = self.pop_stack().get_string();
+                let code = self.pop_stack().get_string();
+                let vars = self.pop_stack().get_string();
+                let vars: Vec<String> = vars.split_whitespace().map(String::from).collect();
+                let span = self.pop_list_f64();
+                let initial = self.pop_stack();
+
+                let system = matches!(initial, Type::List(_));
+                let y0: Vec<f64> = initial
+                    .clone()
+                    .get_list()
+                    .iter_mut()
+                    .map(|x| x.get_number().to_f64())
+                    .collect();
+                let t0 = span.first().copied().unwrap_or(0.0);
+                let t1 = span.get(1).copied().unwrap_or(t0);
+                let step = span.get(2).copied().unwrap_or((t1 - t0) / 100.0);
+                // Step should be finite and go from the start toward the end
+                if step == 0.0
+                    || !step.is_finite()
+                    || !(t1 - t0).is_finite()
+                    || step * (t1 - t0) < 0.0
+                {
+                    self.log_print(format!("Error! step {step} cannot go from {t0} to {t1}\n"));
+                    self.stack.push(Type::Error("invalid-step".to_string()));
+                    return;
+                }
+
+                let solve = match method.as_str() {
+                    "euler" => ode::euler,
+                    "rk4" => ode::rk4,
+                    "rk45" | "dormand-prince" => ode::dormand_prince,
+                    _ => {
+                        self.log_print(format!("Error! unknown ODE method {method}\n"));
+                        self.stack.push(Type::Error("unknown-method".to_string()));
+                        return;
+                    }
+                };
+
+                let mut f = |t: f64, y: &[f64]| {
+                    let state = if system {
+                        Type::List(y.iter().map(|x| Type::Number(Fraction::new(*x))).collect())
+                    } else {
+                        Type::Number(Fraction::new(y[0]))
+                    };
+                    let (time, states) = (vars.first(), vars.get(1));
+                    for (name, value) in [(time, Type::Number(Fraction::new(t))), (states, state)] {
+                        if let Some(name) = name {
+                            self.memory
+                                .entry(name.clone())
+                                .and_modify(|x| *x = value.clone())
+                                .or_insert(value);
+                        }
+                    }
+                    self.evaluate_program(code.clone());
+                    self.pop_stack()
+                        .get_list()
+                        .iter_mut()
+                        .map(|x| x.get_number().to_f64())
+                        .collect()
+                };
+
+                match solve(&mut f, (t0, t1, step), &y0) {
+                    Ok((times, states)) => {
+                        let size = (states.len(), y0.len());
+                        self.stack.push(Type::List(vec![
+                            Type::List(
+                                times
+                                    .iter()
+                                    .map(|x| Type::Number(Fraction::new(*x)))
+                                    .collect(),
+                            ),
+                            Type::Matrix(
+                                states.concat().iter().map(|x| Fraction::new(*x)).collect(),
+                                size,
+                            ),
+                        ]))
+                    }
+                    Err(ode::Failure::LengthMismatch) => {
+                        self.log_print(
+                            "Error! ODE returns other count of components than the state\n"
+                                .to_string(),
+                        );
+                        self.stack.push(Type::Error("length-mismatch".to_string()));
+                    }
+                    Err(ode::Failure::NoConvergence) => {
+                        self.log_print("Error! ODE solution does not converge\n".to_string());
+                        self.stack.push(Type::Error("no-convergence".to_string()));
+                    }
+                }
+            }
+
+            // Commands of series
+
+            // Sum or product of the term over integers n from a to b, exact for rational terms
+            "sum-series" | "product-series" => {
+                let code = self.pop_stack().get_string();
+                let vars = [self.pop_stack().get_string()];
+                let end = self.pop_stack().get_number().to_f64().floor() as isize;
+                let start = self.pop_stack().get_number().to_f64().ceil() as isize;
+
+                let sum = command == "sum-series";
+                let mut result = Fraction::new(if sum { 0.0 } else { 1.0 });
+                for n in start..=end {
+                    let term = self
+                        .call_exact(&vars, &code, &[Fraction::new(n as f64)])
+                        .first()
+                        .copied()
+                        .unwrap_or(Fraction::new(0.0));
+                    result = if sum { result + term } else { result * term };
+                }
+                self.stack.push(Type::Number(result));
+            }
+
+            // Sum of the term over n from a to infinity with acceleration
+            // by (aitken), (wynn) or (richardson), returns [sum error]
+            "infinite-sum" => {
+                let method = self.pop_stack().get_string();
+                let code = self.pop_stack().get_string();
+                let vars = [self.pop_stack().get_string()];
+                let start = self.pop_stack().get_number().to_f64().ceil();
+
+                let (accelerate, doubling): (series::Accelerate, bool) = match method.as_str() {
+                    "aitken" => (series::aitken, false),
+                    "wynn" => (series::wynn, false),
+                    "richardson" => (series::richardson, true),
+                    _ => {
+                        self.log_print(format!("Error! unknown method \"{method}\"\n"));
+                        self.stack.push(Type::Error("unknown-method".to_string()));
+                        return;
+                    }
+                };
+                let mut term = |n: usize| {
+                    self.call_function(&vars, &code, &[start + n as f64])
+                        .first()
+                        .copied()
+                        .unwrap_or(f64::NAN)
+                };
+                match series::infinite_sum(&mut term, accelerate, doubling) {
+                    Some((value, error)) => self.stack.push(Type::List(vec![
+                        Type::Number(Fraction::new(value)),
+                        Type::Number(Fraction::new(error)),
+                    ])),
+                    None => {
+                        self.log_print("Error! series does not converge\n".to_string());
+                        self.stack.push(Type::Error("no-convergence".to_string()));
+                    }
+                }
+            }
+
+            // Limit of the function approaching the point from (left), (right) or (both),
+            // the point may be (inf) or (-inf)
+            "limit" => {
+                let side = self.pop_stack().get_string();
+                let code = self.pop_stack().get_string();
+                let vars = [self.pop_stack().get_string()];
+                let point = self.pop_stack().get_bound();
+
+                let signs: &[f64] = match side.as_str() {
+                    _ if point.is_infinite() => &[1.0],
+                    "left" => &[-1.0],
+                    "right" => &[1.0],
+                    "both" => &[-1.0, 1.0],
+                    _ => {
+                        self.log_print(format!(
+                            "Error! unknown side \"{side}\", use (left), (right) or (both)\n"
+                        ));
+                        self.stack.push(Type::Error("unknown-method".to_string()));
+                        return;
+                    }
+                };
+                let center = Fraction::new(point);
+                let mut limits = Vec::new();
+                for sign in signs {
+                    // Approach by exact fractions as steps are binary fractions of a tenth
+                    let mut f = |h: f64| {
+                        let x = if point.is_infinite() {
+                            Fraction::new(point.signum() / h)
+                        } else {
+                            center + Fraction::new(sign * h * point.abs().max(1.0))
+                        };
+                        self.call_exact(&vars, &code, &[x])
+                            .first()
+                            .map_or(f64::NAN, |x| x.to_f64())
+                    };
+                    match series::limit(&mut f, 0.1) {
+                        Some((value, error)) if error <= 1e-6 * value.abs().max(1.0) => {
+                            limits.push(value)
+                        }
+                        _ => {
+                            self.log_print("Error! function has no finite limit\n".to_string());
+                            self.stack.push(Type::Error("no-limit".to_string()));
+                            return;
+                        }
+                    }
+                }
+                let value = limits.iter().sum::<f64>() / limits.len() as f64;
+                if limits
+                    .iter()
+                    .any(|x| (x - value).abs() > 1e-6 * value.abs().max(1.0))
+                {
+                    self.log_print("Error! left and right limits are different\n".to_string());
+                    self.stack.push(Type::Error("no-limit".to_string()));
+                    return;
+                }
+                self.stack.push(Type::Number(Fraction::new(value)));
+            }
+
+            // Commands of optimization
+
+            // Minimize the function from initial point inside bounds
+            "minimize" => {
+                let method = self.pop_stack().get_string();
+                let code = self.pop_stack().get_string();
+                let vars = self.pop_stack().get_string();
+                let vars: Vec<String> = vars.split_whitespace().map(String::from).collect();
+                let mut bounds = self.pop_stack().get_list();
+                let start = self.pop_list_f64();
+
+                // Bounds are pair of numbers or list of pairs for each variable
+                let bounds: Vec<(f64, f64)> = if bounds.iter().all(|x| matches!(x, Type::List(_))) {
+                    bounds
+                        .iter_mut()
+                        .map(|x| {
+                            let mut pair = x.get_list();
+                            let low = pair
+                                .first_mut()
+                                .map_or(f64::NEG_INFINITY, |x| x.get_bound());
+                            let high = pair.get_mut(1).map_or(f64::INFINITY, |x| x.get_bound());
+                            (low, high)
+                        })
+                        .collect()
+                } else {
+                    let low = bounds
+                        .first_mut()
+                        .map_or(f64::NEG_INFINITY, |x| x.get_bound());
+                    let high = bounds.get_mut(1).map_or(f64::INFINITY, |x| x.get_bound());
+                    vec![(low, high)]
+                };
+                if let Some((low, high)) = bounds
+                    .iter()
+                    .find(|(low, high)| low.is_nan() || high.is_nan() || low > high)
+                {
+                    self.log_print(format!("Error! bounds from {low} to {high} are invalid\n"));
+                    self.stack.push(Type::Error("invalid-bounds".to_string()));
+                    return;
+                }
+
+                let mut f = |x: &[f64]| {
+                    let x = optimize::clamp(x, &bounds);
+                    let value = self.call_function(&vars, &code, &x);
+                    value.first().copied().unwrap_or(f64::NAN)
+                };
+                let (low, high) = bounds
+                    .first()
+                    .copied()
+                    .unwrap_or((f64::NEG_INFINITY, f64::INFINITY));
+                let x0 = start.first().copied().unwrap_or((low + high) / 2.0);
+                let result = match method.as_str() {
+                    "golden" if low.is_finite() && high.is_finite() => {
+                        optimize::golden_section(&mut |x| f(&[x]), low, high)
+                    }
+                    "brent" if low.is_finite() && high.is_finite() => {
+                        optimize::brent(&mut |x| f(&[x]), low, high, x0)
+                    }
+                    "golden" | "brent" => {
+                        self.log_print("Error! search needs finite interval\n".to_string());
+                        self.stack.push(Type::Error("invalid-bounds".to_string()));
+                        return;
+                    }
+                    "nelder-mead" => optimize::nelder_mead(&mut f, &start),
+                    "bfgs" => optimize::bfgs(&mut f, &start),
+                    _ => {
+                        self.log_print(format!("Error! unknown optimization method {method}\n"));
+                        self.stack.push(Type::Error("unknown-method".to_string()));
+                        return;
+                    }
+                };
+
+                let argmin: Vec<Type> = optimize::clamp(&result.argmin, &bounds)
+                    .iter()
+                    .map(|x| Type::Number(Fraction::new(*x)))
+                    .collect();
+                self.stack.push(Type::List(vec![
+                    match method.as_str() {
+                        "golden" | "brent" => argmin[0].clone(),
+                        _ => Type::List(argmin),
+                    },
+                    Type::Number(Fraction::new(result.value)),
+                    Type::Number(Fraction::new(result.iterations as f64)),
+                    Type::Bool(result.converged),
+                ]));
+            }
+
+            // Commands of root finding
+
+            // Root of the function by the method from bracket or initial guesses
+            "find-root" => {
+                let method = self.pop_stack().get_string();
+                let code = self.pop_stack().get_string();
+                let vars = [self.pop_stack().get_string()];
+                let start = self.pop_list_f64();
+
+                let mut f = |x: f64| {
+                    let value = self.call_function(&vars, &code, &[x]);
+                    value.first().copied().unwrap_or(f64::NAN)
+                };
+                let a = start.first().copied().unwrap_or(0.0);
+                let b = start.get(1).copied().unwrap_or(a + 1.0);
+                let result = match method.as_str() {
+                    "bisection" => roots::bisection(&mut f, a, b),
+                    "newton" => roots::newton(&mut f, a),
+                    "secant" => roots::secant(&mut f, a, b),
+                    "brent" => roots::brent(&mut f, a, b),
+                    _ => {
+                        self.log_print(format!("Error! unknown root finding method {method}\n"));
+                        self.stack.push(Type::Error("unknown-method".to_string()));
+                        return;
+                    }
+                };
+
+                match result {
+                    Some((root, iterations)) => self.stack.push(Type::List(vec![
+                        Type::Number(Fraction::new(root)),
+                        Type::Number(Fraction::new(iterations as f64)),
+                    ])),
+                    None => {
+                        self.log_print("Error! root finding does not converge\n".to_string());
+                        self.stack.push(Type::Error("no-convergence".to_string()));
+                    }
+                }
+            }
+
+            // Solve system of nonlinear equations by Newton's method
+            "fsolve" => {
+                let code = self.pop_stack().get_string();
+                let vars = self.pop_stack().get_string();
+                let vars: Vec<String> = vars.split_whitespace().map(String::from).collect();
+                let start = self.pop_list_f64();
+
+                let mut f = |x: &[f64]| self.call_function(&vars, &code, x);
+                match roots::newton_system(&mut f, &start) {
+                    Some((root, iterations)) => self.stack.push(Type::List(vec![
+                        Type::List(
+                            root.iter()
+                                .map(|x| Type::Number(Fraction::new(*x)))
+                                .collect(),
+                        ),
+                        Type::Number(Fraction::new(iterations as f64)),
+                    ])),
+                    None => {
+                        self.log_print("Error! root finding does not converge\n".to_string());
+                        self.stack.push(Type::Error("no-convergence".to_string()));
+                    }
+                }
+            }
+
+            // Commands of graph
+            // Nodes are referred by indices from 0
+
+            // Make graph from adjacency matrix of edge weights, or of isolated nodes of the count,
+            // which is directed unless (undirected) is given
+            "graph" => {
+                let (directed, mut data) = match self.pop_stack() {
+                    Type::String(kind) => match kind.as_str() {
+                        "directed" => (true, self.pop_stack()),
+                        "undirected" => (false, self.pop_stack()),
+                        _ => {
+                            self.log_print(format!(
+                                "Error! unknown kind of graph \"{kind}\", use (directed) or (undirected)\n"
+                            ));
+                            self.stack.push(Type::Error("unknown-kind".to_string()));
+                            return;
+                        }
+                    },
+                    other => (true, other),
+                };
+                let network = match data {
+                    Type::Number(count) => {
+                        let mut network = Network::new(directed);
+                        for i in 0..count.to_f64().max(0.0) as usize {
+                            network.add_node(i.to_string());
+                        }
+                        network
+                    }
+                    _ => {
+                        let (data, (rows, cols)) = data.get_matrix();
+                        if rows != cols {
+                            self.log_print("Error! adjacency matrix must be square\n".to_string());
+                            self.stack.push(Type::Error("shape-mismatch".to_string()));
+                            return;
+                        }
+                        Network::from_matrix(&data, rows, directed)
+                    }
+                };
+                self.stack.push(Type::Graph(network));
+            }
+
+            // Get labels of nodes
+            "nodes" => {
+                if let Some(network) = self.pop_graph() {
+                    let labels = network.labels().into_iter().map(Type::String).collect();
+                    self.stack.push(Type::List(labels));
+                }
+            }
+
+            // Get edges as list of [from to weight]
+            "edges" => {
+                if let Some(network) = self.pop_graph() {
+                    let edges = network
+                        .edges()
+                        .into_iter()
+                        .map(|(a, b, weight)| {
+                            Type::List(vec![
+                                Type::Number(Fraction::new(a as f64)),
+                                Type::Number(Fraction::new(b as f64)),
+                                Type::Number(Fraction::new(weight)),
+                            ])
+                        })
+                        .collect();
+                    self.stack.push(Type::List(edges));
+                }
+            }
+
+            // Relabel nodes by the list
+            "set-labels" => {
+                let mut labels = self.pop_stack().get_list();
+                let labels: Vec<String> = labels.iter_mut().map(|x| x.get_string()).collect();
+                if let Some(mut network) = self.pop_graph() {
+                    match network.set_labels(labels) {
+                        Some(()) => self.stack.push(Type::Graph(network)),
+                        None => {
+                            self.log_print("Error! count of labels and nodes differ\n".to_string());
+                            self.stack.push(Type::Error("length-mismatch".to_string()));
+                        }
+                    }
+                }
+            }
+
+            // Add node of the label
+            "add-node" => {
+                let label = self.pop_stack().get_string();
+                if let Some(mut network) = self.pop_graph() {
+                    network.add_node(label);
+                    self.stack.push(Type::Graph(network));
+                }
+            }
+
+            // Add edge of the weight between nodes
+            "add-edge" => {
+                let weight = self.pop_stack().get_number().to_f64();
+                let to = self.pop_node();
+                let from = self.pop_node();
+                if let Some(mut network) = self.pop_graph() {
+                    let added = from
+                        .zip(to)
+                        .and_then(|(from, to)| network.add_edge(from, to, weight));
+                    match added {
+                        Some(()) => self.stack.push(Type::Graph(network)),
+                        None => self.push_invalid_node(),
+                    }
+                }
+            }
+
+            // Remove edge between nodes
+            "remove-edge" => {
+                let to = self.pop_node();
+                let from = self.pop_node();
+                if let Some(mut network) = self.pop_graph() {
+                    let (Some(from), Some(to)) = (from, to) else {
+                        self.push_invalid_node();
+                        return;
+                    };
+                    match network.remove_edge(from, to) {
+                        Some(_) => self.stack.push(Type::Graph(network)),
+                        None => {
+                            self.log_print(format!(
+                                "Error! edge from {from} to {to} is not found\n"
+                            ));
+                            self.stack.push(Type::Error("edge-not-found".to_string()));
+                        }
+                    }
+                }
+            }
+
+            // Get adjacent nodes, successors in directed graph
+            "neighbors" => {
+                let node = self.pop_node();
+                if let Some(network) = self.pop_graph() {
+                    match node.and_then(|node| network.neighbors(node)) {
+                        Some(neighbors) => self.stack.push(node_list(neighbors)),
+                        None => self.push_invalid_node(),
+                    }
+                }
+            }
+
+            // Get degree of node, sum of in-degree and out-degree in directed graph
+            "degree" => {
+                let node = self.pop_node();
+                if let Some(network) = self.pop_graph() {
+                    match node.and_then(|node| network.degree(node)) {
+                        Some(degree) => self.stack.push(Type::Number(Fraction::new(degree as f64))),
+                        None => self.push_invalid_node(),
+                    }
+                }
+            }
+
+            // Convert graph to adjacency matrix of weights
+            "adjacency-matrix" => {
+                let mut value = self.pop_stack();
+                let (data, size) = value.get_matrix();
+                self.stack.push(Type::Matrix(data, size));
+            }
+
+            // Convert graph to DOT language, edge list or GraphML, to be saved by write-file
+            "to-dot" | "to-edge-list" | "to-graphml" => {
+                if let Some(network) = self.pop_graph() {
+                    self.stack.push(Type::String(match command.as_str() {
+                        "to-dot" => network.to_dot(),
+                        "to-edge-list" => network.to_edge_list(),
+                        _ => network.to_graphml(),
+                    }));
+                }
+            }
+
+            // Parse graph from edge list of lines like "from to weight", which is directed
+            // unless (undirected) is given, or from GraphML, read by read-file
+            "from-edge-list" | "from-graphml" => {
+                let mut text = self.pop_stack().get_string();
+                let mut directed = true;
+                if command == "from-edge-list"
+                    && ["directed", "undirected"].contains(&text.as_str())
+                {
+                    directed = text == "directed";
+                    text = self.pop_stack().get_string();
+                }
+                let network = if command == "from-edge-list" {
+                    Network::from_edge_list(&text, directed)
+                } else {
+                    Network::from_graphml(&text)
+                };
+                match network {
+                    Ok(network) => self.stack.push(Type::Graph(network)),
+                    Err(e) => {
+                        self.log_print(format!("Error! {e}\n"));
+                        self.stack.push(Type::Error("invalid-graph".to_string()));
+                    }
+                }
+            }
+
+            // Generate undirected graph of the count of nodes
+            "complete-graph" | "cycle-graph" | "path-graph" | "star-graph" => {
+                let n = self.pop_stack().get_number().to_f64().max(0.0) as usize;
+                // Pairs of nodes of complete graph should be countable
+                if command == "complete-graph" && n.checked_mul(n.saturating_sub(1)).is_none() {
+                    self.log_print(format!("Error! complete graph of {n} nodes is too large\n"));
+                    self.stack.push(Type::Error("invalid-size".to_string()));
+                    return;
+                }
+                self.stack.push(Type::Graph(match command.as_str() {
+                    "complete-graph" => Network::complete(n),
+                    "cycle-graph" => Network::cycle(n),
+                    "path-graph" => Network::path(n),
+                    _ => Network::star(n),
+                }));
+            }
+
+            // Generate grid graph of the rows and columns
+            "grid-graph" => {
+                let cols = self.pop_stack().get_number().to_f64().max(0.0) as usize;
+                let rows = self.pop_stack().get_number().to_f64().max(0.0) as usize;
+                if rows.checked_mul(cols).is_none() {
+                    self.log_print(format!("Error! grid of {rows} by {cols} is too large\n"));
+                    self.stack.push(Type::Error("invalid-size".to_string()));
+                    return;
+                }
+                self.stack.push(Type::Graph(Network::grid(rows, cols)));
+            }
+
+            // Generate Erdős–Rényi random graph of the count of nodes and probability of edges
+            "random-graph" => {
+                let p = self.pop_stack().get_number().to_f64();
+                let n = self.pop_stack().get_number().to_f64().max(0.0) as usize;
+                if n.checked_mul(n.saturating_sub(1)).is_none() {
+                    self.log_print(format!("Error! random graph of {n} nodes is too large\n"));
+                    self.stack.push(Type::Error("invalid-size".to_string()));
+                    return;
+                }
+                self.stack
+                    .push(Type::Graph(Network::random(n, p, &mut self.rng)));
+            }
+
+            // Shortest distances from the source to each node, unreachable one is 1/0,
+            // Dijkstra's algorithm requires nonnegative weights
+            "dijkstra" | "bellman-ford" => {
+                let source = self.pop_node();
+                let Some(network) = self.pop_graph() else {
+                    return;
+                };
+                let Some(source) = source.filter(|source| *source < network.len()) else {
+                    self.push_invalid_node();
+                    return;
+                };
+                let distances = if command == "dijkstra" {
+                    network.dijkstra(source)
+                } else {
+                    network.bellman_ford(source)
+                };
+                match distances {
+                    Some(distances) => self.stack.push(Type::List(
+                        distances
+                            .into_iter()
+                            .map(|x| Type::Number(graph::distance(x)))
+                            .collect(),
+                    )),
+                    None if command == "dijkstra" => {
+                        self.log_print("Error! weight of edge is negative\n".to_string());
+                        self.stack.push(Type::Error("negative-weight".to_string()));
+                    }
+                    None => {
+                        self.log_print("Error! graph has negative cycle\n".to_string());
+                        self.stack.push(Type::Error("negative-cycle".to_string()));
+                    }
+                }
+            }
+
+            // Matrix of shortest distances between all pairs, unreachable one is 1/0
+            "floyd-warshall" => {
+                if let Some(network) = self.pop_graph() {
+                    match network.floyd_warshall() {
+                        Some(distances) => self.stack.push(Type::Matrix(
+                            distances.into_iter().map(graph::distance).collect(),
+                            (network.len(), network.len()),
+                        )),
+                        None => {
+                            self.log_print("Error! graph has negative cycle\n".to_string());
+                            self.stack.push(Type::Error("negative-cycle".to_string()));
+                        }
+                    }
+                }
+            }
+
+            // Order of nodes visited by breadth-first or depth-first search from the start
+            "bfs" | "dfs" => {
+                let start = self.pop_node();
+                if let Some(network) = self.pop_graph() {
+                    match start.and_then(|start| network.traverse(start, command == "bfs")) {
+                        Some(order) => self.stack.push(node_list(order)),
+                        None => self.push_invalid_node(),
+                    }
+                }
+            }
+
+            // Topological order of nodes of directed acyclic graph
+            "toposort" => {
+                if let Some(network) = self.pop_graph() {
+                    match network.toposort() {
+                        Some(order) => self.stack.push(node_list(order)),
+                        None => {
+                            self.log_print("Error! graph is not directed acyclic\n".to_string());
+                            self.stack.push(Type::Error("not-acyclic".to_string()));
+                        }
+                    }
+                }
+            }
+
+            // List of groups of connected nodes, weakly connected in directed graph
+            "connected-components" | "strongly-connected-components" => {
+                if let Some(network) = self.pop_graph() {
+                    let groups = if command == "connected-components" {
+                        network.connected_components()
+                    } else {
+                        network.strongly_connected_components()
+                    };
+                    let groups = groups.into_iter().map(node_list).collect();
+                    self.stack.push(Type::List(groups));
+                }
+            }
+
+            // Minimum spanning forest as undirected graph
+            "min-spanning-tree" => {
+                if let Some(network) = self.pop_graph() {
+                    self.stack.push(Type::Graph(network.min_spanning_tree()));
+                }
+            }
+
+            // Judge does graph have a cycle
+            "has-cycle" => {
+                if let Some(network) = self.pop_graph() {
+                    self.stack.push(Type::Bool(network.has_cycle()));
+                }
+            }
+
+            // Maximum flow from source to sink on capacity matrix as [value flow-matrix],
+            // or minimum cut as [value [nodes-of-source-side] [[from to] ...]]
+            "max-flow" | "min-cut" => {
+                let sink = self.pop_node();
+                let source = self.pop_node();
+                let (capacity, (rows, cols)) = self.pop_stack().get_matrix();
+                if rows != cols {
+                    self.log_print("Error! capacity matrix must be square\n".to_string());
+                    self.stack.push(Type::Error("shape-mismatch".to_string()));
+                    return;
+                }
+                let inside = |node: Option<usize>| node.filter(|node| *node < rows);
+                let (Some(source), Some(sink)) = (inside(source), inside(sink)) else {
+                    self.push_invalid_node();
+                    return;
+                };
+
+                let result = flow::max_flow(&capacity, rows, source, sink);
+                let value = Type::Number(result.value);
+                if command == "max-flow" {
+                    let flow = Type::Matrix(result.flow, (rows, rows));
+                    self.stack.push(Type::List(vec![value, flow]));
+                    return;
+                }
+                let side = &result.source_side;
+                let mut edges = Vec::new();
+                for (i, j) in (0..rows * rows).map(|k| (k / rows, k % rows)) {
+                    if side[i] && !side[j] && capacity[i * rows + j].numerator != 0 {
+                        edges.push(node_list(vec![i, j]));
+                    }
+                }
+                let nodes = node_list((0..rows).filter(|i| side[*i]).collect());
+                self.stack
+                    .push(Type::List(vec![value, nodes, Type::List(edges)]));
+            }
+
+            // Maximum matching of bipartite graph by matrix of rows and columns,
+            // whose nonzero elements are edges, as [size [[row column] ...]]
+            "max-matching" => {
+                let (data, size) = self.pop_stack().get_matrix();
+                let adjacent: Vec<bool> = data.iter().map(|x| x.numerator != 0).collect();
+                let pairs: Vec<Type> = flow::max_matching(&adjacent, size)
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(row, col)| Some(node_list(vec![row, col?])))
+                    .collect();
+                self.stack.push(Type::List(vec![
+                    Type::Number(Fraction::new(pairs.len() as f64)),
+                    Type::List(pairs),
+                ]));
+            }
+
+            // Assignment of rows to columns minimizing total of cost matrix
+            // as [total [column-of-each-row]]
+            "assignment" => {
+                let (cost, size) = self.pop_stack().get_matrix();
+                if size.0 > size.1 {
+                    self.log_print("Error! rows outnumber columns of cost matrix\n".to_string());
+                    self.stack.push(Type::Error("shape-mismatch".to_string()));
+                    return;
+                }
+                // Infinite cost forbids the pair, but undefined one like 0/0 cannot be compared
+                let values: Vec<f64> = cost.iter().map(|x| x.to_f64()).collect();
+                if values.iter().any(|x| x.is_nan()) {
+                    self.log_print("Error! cost matrix has undefined cost\n".to_string());
+                    self.stack.push(Type::Error("invalid-cost".to_string()));
+                    return;
+                }
+                match flow::assignment(&values, size) {
+                    Some(assigned) => {
+                        let total = assigned
+                            .iter()
+                            .enumerate()
+                            .fold(Fraction::new(0.0), |acc, (i, j)| acc + cost[i * size.1 + j]);
+                        self.stack
+                            .push(Type::List(vec![Type::Number(total), node_list(assigned)]));
+                    }
+                    None => {
+                        self.log_print("Error! every assignment has infinite cost\n".to_string());
+                        self.stack.push(Type::Error("invalid-cost".to_string()));
+                    }
+                }
+            }
+
+            // Centrality of each node, PageRank is of damping factor 0.85,
+            // betweenness and closeness are by counts of edges
+            "pagerank" | "betweenness" | "closeness" | "eigenvector-centrality" => {
+                let Some(network) = self.pop_graph() else {
+                    return;
+                };
+                let centrality = match command.as_str() {
+                    "pagerank" => network.pagerank(0.85),
+                    "betweenness" => Some(network.betweenness()),
+                    "closeness" => Some(network.closeness()),
+                    _ => network.eigenvector_centrality(),
+                };
+                match centrality {
+                    Some(centrality) => self.stack.push(Type::List(
+                        centrality
+                            .into_iter()
+                            .map(|x| Type::Number(Fraction::new(x)))
+                            .collect(),
+                    )),
+                    None => {
+                        self.log_print(format!("Error! {command} does not converge\n"));
+                        self.stack.push(Type::Error("no-convergence".to_string()));
+                    }
+                }
+            }
+
+            // Draw bar chart of the list, which is saved to the file if its path is given
+            "bar-chart" => {
+                let output = self.pop_output();
+                let data: Vec<f64> = self
+                    .pop_stack()
+                    .get_list()
+                    .iter_mut()
+                    .map(|x| x.get_number().to_f64())
+                    .collect();
+                let mut figure = Figure::new();
+                figure.axes2d().boxes(1..=data.len(), data, &[]);
+                figure.set_title("Bar Chart - NumStack");
+                self.render(&mut figure, output);
+            }
+
+            // Draw histogram of the data, whose bins are given like histogram, and path of file
+            "histogram-chart" => {
+                let output = self.pop_output();
+                match self.pop_histogram() {
+                    Ok((edges, counts)) => {
+                        let edges: Vec<f64> = edges.iter().map(|x| x.to_f64()).collect();
+                        let centers = edges.windows(2).map(|edge| (edge[0] + edge[1]) / 2.0);
+                        let widths = edges.windows(2).map(|edge| edge[1] - edge[0]);
+                        let mut figure = Figure::new();
+                        figure
+                            .axes2d()
+                            .boxes_set_width(centers, counts, widths, &[]);
+                        figure.set_title("Histogram - NumStack");
+                        self.render(&mut figure, output);
+                    }
+                    Err(error) => self.stack.push(error),
+                }
+            }
+
+            // Draw line chart of the list or the columns of the matrix
+            "line-chart" => {
+                let output = self.pop_output();
+                let mut figure = Figure::new();
+                match self.pop_stack() {
+                    // Draw each column of the matrix as a line
+                    Type::Matrix(data, (rows, cols)) => {
+                        let axes = figure.axes2d();
+                        for col in 0..cols {
+                            let column = (0..rows).map(|row| data[row * cols + col].to_f64());
+                            axes.lines(1..=rows, column, &[]);
+                        }
+                    }
+                    mut list => {
+                        let data: Vec<f64> = list
+                            .get_list()
+                            .iter_mut()
+                            .map(|x| x.get_number().to_f64())
+                            .collect();
+                        figure.axes2d().lines(1..=data.len(), data, &[]);
+                    }
+                }
+                figure.set_title("Line Chart - NumStack");
+                self.render(&mut figure, output);
+            }
+
+            // Plot series with options like [(title Growth) (xlabel t) (logy) (grid) (output a.png)],
+            // where each series is [ys] or [xs ys] or [xs ys errors] with (style scatter) (legend A),
+            // or a matrix whose first column is x and the others are series of y
+            "plot" => {
+                let options: Vec<String> = self
+                    .pop_stack()
+                    .get_list()
+                    .iter_mut()
+                    .map(|x| x.get_string())
+                    .collect();
+                let series = match self.pop_stack() {
+                    Type::Matrix(data, (rows, cols)) => {
+                        let column = |col: usize| -> Vec<f64> {
+                            (0..rows)
+                                .map(|row| data[row * cols + col].to_f64())
+                                .collect()
+                        };
+                        if cols == 1 {
+                            vec![plot::Series::new(vec![column(0)], &[])]
+                        } else {
+                            (1..cols)
+                                .map(|col| plot::Series::new(vec![column(0), column(col)], &[]))
+                                .collect()
+                        }
+                    }
+                    mut data => {
+                        let mut data = data.get_list();
+                        // Single list of numbers is a series of y
+                        if data.iter().all(|x| matches!(x, Type::Number(_))) {
+                            data = vec![Type::List(vec![Type::List(data)])];
+                        }
+                        data.iter_mut()
+                            .map(|series| {
+                                let Type::List(items) = series else {
+                                    return Err(format!(
+                                        "series {} is not a list",
+                                        series.display()
+                                    ));
+                                };
+                                let mut lists = vec![];
+                                let mut options = vec![];
+                                for item in items.iter_mut() {
+                                    match item {
+                                        Type::String(option) => options.push(option.clone()),
+                                        Type::List(list) => lists.push(
+                                            list.iter_mut()
+                                                .map(|x| x.get_number().to_f64())
+                                                .collect(),
+                                        ),
+                                        other => {
+                                            return Err(format!(
+                                                "item {} of series is neither list nor option",
+                                                other.display()
+                                            ))
+                                        }
+                                    }
+                                }
+                                plot::Series::new(lists, &options)
+                            })
+                            .collect()
+                    }
+                };
+                let plotted = series
+                    .into_iter()
+                    .collect::<Result<Vec<plot::Series>, String>>()
+                    .and_then(|series| Ok((series, plot::Options::parse(&options)?)));
+                match plotted {
+                    Ok((series, options)) => {
+                        let mut figure = plot::figure(&series, &options);
+                        self.render(&mut figure, options.output);
+                    }
+                    Err(e) => {
+                        self.log_print(format!("Error! {e}\n"));
+                        self.stack.push(Type::Error("invalid-plot".to_string()));
+                    }
+                }
+            }
+
+            // If it is not recognized as a command, use it as a string.
+            _ => self.stack.push(Type::String(command)),
+        }
+    }
+
+    /// Density (or mass) and cumulative probability of the value,
+    /// value of the probability by quantile, or list of n samples of the distribution
+    fn execute_distribution(&mut self, name: &str, function: &str) {
+        let parameters = self.pop_list_f64();
+        let mut value = self.pop_stack();
+        let distribution = match distribution::Distribution::new(name, &parameters) {
+            Some(distribution) => distribution,
+            None => {
+                self.log_print(format!("Error! parameters of {name} are invalid\n"));
+                self.stack
+                    .push(Type::Error("invalid-parameters".to_string()));
+                return;
+            }
+        };
+
+        if function == "sample" {
+            let count = value.get_number().to_f64().max(0.0) as usize;
+            let samples = (0..count)
+                .map(|_| Type::Number(Fraction::new(distribution.sample(&mut self.rng))))
+                .collect();
+            self.stack.push(Type::List(samples));
+            return;
+        }
+
+        let defined = std::cell::Cell::new(true);
+        let result = value.map_exact(&|x| {
+            let x = x.to_f64();
+            let y = match function {
+                "cdf" => distribution.cdf(x),
+                "quantile" => distribution.quantile(x),
+                _ => distribution.density(x),
+            };
+            defined.set(defined.get() && y.is_finite());
+            Fraction::new(y)
+        });
+        if defined.get() {
+            self.stack.push(result);
+        } else {
+            self.log_print(format!("Error! {name}-{function} is out of domain\n"));
+            self.stack.push(Type::Error("out-of-domain".to_string()));
+        }
+    }
+
+    /// Uniform random fraction in [0, 1), resampling values which round up to 1
+    fn random_unit(&mut self) -> Fraction {
+        loop {
+            let value = Fraction::new(self.rng.gen::<f64>());
+            if value.numerator < value.denominator {
+                return value;
+            }
+        }
+    }
+
+    /// Evaluate code as function of the variables bound to the arguments
+    fn call_function(&mut self, vars: &[String], code: &str, args: &[f64]) -> Vec<f64> {
+        let args: Vec<Fraction> = args.iter().map(|x| Fraction::new(*x)).collect();
+        self.call_exact(vars, code, &args)
+            .iter()
+            .map(|x| x.to_f64())
+            .collect()
+    }
+
+    /// Evaluate code as function of the variables bound to the fractions without approximation
+    fn call_exact(&mut self, vars: &[String], code: &str, args: &[Fraction]) -> Vec<Fraction> {
+        for (name, arg) in vars.iter().zip(args) {
+            let value = Type::Number(*arg);
+            self.memory
+                .entry(name.clone())
+                .and_modify(|x| *x = value.clone())
+                .or_insert(value);
+        }
+        self.evaluate_program(code.to_string());
+        self.pop_stack()
+            .get_list()
+            .iter_mut()
+            .map(|x| x.get_number())
+            .collect()
+    }
+
+    /// Apply statistic to the list, or to each column of the matrix
+    fn statistic(&mut self, data: Type, f: &dyn Fn(&[Fraction]) -> Option<Fraction>) -> Type {
+        let result = match data {
+            Type::Matrix(data, (rows, cols)) => (0..cols)
+                .map(|j| {
+                    let column: Vec<Fraction> = (0..rows).map(|i| data[i * cols + j]).collect();
+                    f(&column).map(Type::Number)
+                })
+                .collect::<Option<Vec<Type>>>()
+                .map(Type::List),
+            mut other => {
+                let list: Vec<Fraction> = other
+                    .get_list()
+                    .iter_mut()
+                    .map(|x| x.get_number())
+                    .collect();
+                f(&list).map(Type::Number)
+            }
+        };
+        result.unwrap_or_else(|| {
+            self.log_print("Error! statistic is undefined for the data\n".to_string());
+            Type::Error("undefined-statistic".to_string())
+        })
+    }
+
+    /// Pop graph, or directed one of square adjacency matrix, pushes error if it is not
+    fn pop_graph(&mut self) -> Option<Network> {
+        match self.pop_stack() {
+            Type::Graph(network) => Some(network),
+            Type::Matrix(data, (rows, cols)) if rows == cols => {
+                Some(Network::from_matrix(&data, rows, true))
+            }
+            _ => {
+                self.log_print("Error! value is not a graph\n".to_string());
+                self.stack.push(Type::Error("not-graph".to_string()));
+                None
+            }
+        }
+    }
+
+    /// Pop index of node, which is None if it is negative or not an integer
+    fn pop_node(&mut self) -> Option<usize> {
+        let index = self.pop_stack().get_number();
+        (index.denominator == 1 && index.numerator >= 0).then_some(index.numerator as usize)
+    }
+
+    /// Push error of node out of the graph
+    fn push_invalid_node(&mut self) {
+        self.log_print("Error! node is not in the graph\n".to_string());
+        self.stack.push(Type::Error("invalid-node".to_string()));
+    }
+
+    /// Pop table, pushes error if it is not
+    fn pop_table(&mut self) -> Option<Table> {
+        match self.pop_stack() {
+            Type::Table(table) => Some(table),
+            _ => {
+                self.log_print("Error! value is not a table\n".to_string());
+                self.stack.push(Type::Error("not-table".to_string()));
+                None
+            }
+        }
+    }
+
+    /// Push resulting table, which fails if the column is unknown
+    fn push_table(&mut self, table: Option<Table>, column: &str) {
+        match table {
+            Some(table) => self.stack.push(Type::Table(table)),
+            None => {
+                self.log_print(format!("Error! column {column} is unknown\n"));
+                self.stack.push(Type::Error("unknown-column".to_string()));
+            }
+        }
+    }
+
+    /// Pop the data and bins of histogram, returns the edges and counts of bins
+    fn pop_histogram(&mut self) -> Result<(Vec<Fraction>, Vec<usize>), Type> {
+        let bins = self.pop_stack();
+        let data: Vec<Fraction> = self
+            .pop_stack()
+            .get_list()
+            .iter_mut()
+            .map(|x| x.get_number())
+            .collect();
+
+        let edges = match bins {
+            Type::List(mut edges) => {
+                let edges: Vec<Fraction> = edges.iter_mut().map(|x| x.get_number()).collect();
+                let ascending = edges
+                    .windows(2)
+                    .all(|edge| statistics::compare(&edge[0], &edge[1]).is_lt());
+                (edges.len() >= 2 && ascending).then_some(edges)
+            }
+            Type::String(rule) => {
+                let rule: Vec<&str> = rule.split_whitespace().collect();
+                let bins = match rule[..] {
+                    ["sturges"] => Some(statistics::Bins::Sturges),
+                    ["freedman-diaconis"] => Some(statistics::Bins::FreedmanDiaconis),
+                    ["width", width] => width
+                        .parse::<f64>()
+                        .ok()
+                        .map(|width| statistics::Bins::Width(Fraction::new(width))),
+                    _ => None,
+                };
+                let Some(bins) = bins else {
+                    self.log_print(format!("Error! unknown rule of bins {rule:?}\n"));
+                    return Err(Type::Error("unknown-method".to_string()));
+                };
+                statistics::bin_edges(&data, bins)
+            }
+            mut count => {
+                let count = count.get_number().to_f64().max(0.0) as usize;
+                statistics::bin_edges(&data, statistics::Bins::Count(count))
+            }
+        };
+        match edges {
+            Some(edges) => {
+                let counts = statistics::histogram(&data, &edges);
+                Ok((edges, counts))
+            }
+            None => {
+                self.log_print("Error! bins are invalid for the data\n".to_string());
+                Err(Type::Error("invalid-bins".to_string()))
+            }
+        }
+    }
+
+    /// Pop path of the chart file if the top of stack is a string with the extension
+    /// of png, svg, pdf or gp (gnuplot script), otherwise the chart is shown on display
+    fn pop_output(&mut self) -> Option<String> {
+        let Some(Type::String(path)) = self.stack.last() else {
+            return None;
+        };
+        let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+        if !["png", "svg", "pdf", "gp"].contains(&extension.as_str()) {
+            return None;
+        }
+        Some(self.pop_stack().get_string())
+    }
+
+    /// Show the figure or save it to the file by the terminal of its extension,
+    /// whose gnuplot script is written instead if gnuplot is not available
+    fn render(&mut self, figure: &mut Figure, output: Option<String>) {
+        let Some(output) = output else {
+            if let Err(e) = figure.show() {
+                self.log_print(format!("Error! {e}\n"));
+                self.stack.push(Type::Error("render-failed".to_string()));
+            }
+            return;
+        };
+        let path = Path::new(&output);
+        let extension = path.extension().and_then(|x| x.to_str()).unwrap_or("");
+        let terminal = match extension.to_lowercase().as_str() {
+            "png" => "pngcairo size 800,600",
+            "svg" => "svg size 800,600",
+            "pdf" => "pdfcairo size 8in,6in",
+            _ => "",
+        };
+        figure.set_terminal(terminal, if terminal.is_empty() { "" } else { &output });
+        if !terminal.is_empty() {
+            match figure.show() {
+                Ok(_) => return,
+                Err(e) => self.log_print(format!("Error! {e}\n")),
+            }
+        }
+
+        // Write the script which renders the chart when piped to gnuplot, with data inline
+        // because the temporary directory of data is removed with the figure
+        let script = path.with_extension("gp");
+        figure.set_data_directory(None);
+        let result = File::create(&script).map(|mut file| {
+            figure.echo(&mut file);
+        });
+        if let Err(e) = result {
+            self.log_print(format!("Error! {e}\n"));
+            self.stack.push(Type::Error("create-file".to_string()));
+        } else if !terminal.is_empty() {
+            self.log_print(format!(
+                "Error! gnuplot is not found, so its script is written to {}\n",
+                script.display()
+            ));
+            self.stack
+                .push(Type::Error("gnuplot-not-found".to_string()));
+        }
+    }
+
+    /// Push result of hypothesis test as [statistic p-value degrees-of-freedom]
+    fn push_test(&mut self, command: &str, test: Option<hypothesis::Test>) {
+        match test {
+            Some(test) => {
+                let number = |x: f64| Type::Number(Fraction::new(x));
+                let degrees = match test.degrees[..] {
+                    [degrees] => number(degrees),
+                    _ => Type::List(test.degrees.into_iter().map(number).collect()),
+                };
+                self.stack.push(Type::List(vec![
+                    number(test.statistic),
+                    number(test.p_value),
+                    degrees,
+                ]));
+            }
+            None => {
+                self.log_print(format!("Error! {command} is undefined for the data\n"));
+                self.stack
+                    .push(Type::Error("undefined-statistic".to_string()));
+            }
+        }
+    }
+
+    /// Pop list of numbers as floating-point values
+    fn pop_list_f64(&mut self) -> Vec<f64> {
+        self.pop_stack()
+            .get_list()
+            .iter_mut()
+            .map(|x| x.get_number().to_f64())
+            .collect()
+    }
+
+    /// Pop lists of x and y coordinates of samples, whose x are distinct,
+    /// sorted in ascending order of x if required
+    fn pop_samples(
+        &mut self,
+        minimum: usize,
+        sorted: bool,
+    ) -> Result<(Vec<Fraction>, Vec<Fraction>), Type> {
+        let ys: Vec<Fraction> = self
+            .pop_stack()
+            .get_list()
+            .iter_mut()
+            .map(|x| x.get_number())
+            .collect();
+        let xs: Vec<Fraction> = self
+            .pop_stack()
+            .get_list()
+            .iter_mut()
+            .map(|x| x.get_number())
+            .collect();
+        if xs.len() != ys.len() {
+            self.log_print("Error! lengths of x and y are different\n".to_string());
+            return Err(Type::Error("length-mismatch".to_string()));
+        }
+        if xs.len() < minimum {
+            self.log_print(format!(
+                "Error! interpolation needs {minimum} samples at least\n"
+            ));
+            return Err(Type::Error("too-few-samples".to_string()));
+        }
+
+        let mut samples: Vec<(Fraction, Fraction)> = xs.into_iter().zip(ys).collect();
+        if sorted {
+            samples.sort_by(|a, b| a.0.to_f64().total_cmp(&b.0.to_f64()));
+        }
+        let distinct = (0..samples.len())
+            .all(|i| (0..i).all(|j| (samples[i].0 - samples[j].0).numerator != 0));
+        if !distinct {
+            self.log_print("Error! x coordinates of samples are duplicated\n".to_string());
+            return Err(Type::Error("duplicate-samples".to_string()));
+        }
+        Ok(samples.into_iter().unzip())
+    }
+
+    /// Pop lists of x and y of the data as floating-point values
+    fn pop_data_f64(&mut self) -> Result<(Vec<f64>, Vec<f64>), Type> {
+        let ys = self.pop_list_f64();
+        let xs = self.pop_list_f64();
+        if xs.len() != ys.len() {
+            self.log_print("Error! lengths of x and y are different\n".to_string());
+            return Err(Type::Error("length-mismatch".to_string()));
+        }
+        if xs.is_empty() {
+            self.log_print("Error! data is empty\n".to_string());
+            return Err(Type::Error("too-few-samples".to_string()));
+        }
+        Ok((xs, ys))
+    }
+
+    /// Pop list of complex numbers, each is [real imaginary] or real number
+    fn pop_complex_list(&mut self) -> Vec<nalgebra::Complex<f64>> {
+        self.pop_stack()
+            .get_list()
+            .iter_mut()
+            .map(|x| match x {
+                Type::List(pair) if pair.len() == 2 => nalgebra::Complex::new(
+                    pair[0].get_number().to_f64(),
+                    pair[1].get_number().to_f64(),
+                ),
+                other => nalgebra::Complex::new(other.get_number().to_f64(), 0.0),
+            })
+            .collect()
+    }
+
+    /// Push list of complex numbers as [real imaginary]
+    fn push_complex_list(&mut self, list: Vec<nalgebra::Complex<f64>>) {
+        self.stack.push(Type::List(
+            list.iter()
+                .map(|z| {
+                    Type::List(vec![
+                        Type::Number(Fraction::new(z.re)),
+                        Type::Number(Fraction::new(z.im)),
+                    ])
+                })
+                .collect(),
+        ));
+    }
+
+    /// Push matrix from floating-point values, or error if any is not finite
+    fn push_matrix_f64(&mut self, data: Vec<f64>, size: (usize, usize)) {
+        if data.iter().all(|x| x.is_finite()) {
+            self.stack.push(Type::Matrix(
+                data.iter().map(|x| Fraction::new(*x)).collect(),
+                size,
+            ))
+        } else {
+            self.log_print("Error! function is not differentiable\n".to_string());
+            self.stack
+                .push(Type::Error("not-differentiable".to_string()));
+        }
+    }
+
+    /// Apply arithmetic operator element-wise over lists, matrices and arrays
+    fn arithmetic(&mut self, a: Type, b: Type, op: fn(Fraction, Fraction) -> Fraction) -> Type {
+        match (a, b) {
+            (mut a @ Type::Array(_), mut b) | (mut a, mut b @ Type::Array(_)) => {
+                match (a.get_array(), b.get_array()) {
+                    (Some(a), Some(b)) => match a.zip_with(&b, op) {
+                        Some(array) => Type::Array(array),
+                        None => {
+                            self.log_print("Error! shapes cannot be broadcast\n".to_string());
+                            Type::Error("shape-mismatch".to_string())
+                        }
+                    },
+                    _ => {
+                        self.log_print("Error! list is ragged to be array\n".to_string());
+                        Type::Error("ragged-array".to_string())
+                    }
+                }
+            }
+            (Type::Matrix(a, size_a), Type::Matrix(b, size_b)) => {
+                if size_a == size_b {
+                    Type::Matrix(
+                        a.into_iter().zip(b).map(|(x, y)| op(x, y)).collect(),
+                        size_a,
+                    )
+                } else {
+                    self.log_print("Error! matrices are different size\n".to_string());
+                    Type::Error("shape-mismatch".to_string())
+                }
+            }
+            (mut a @ Type::Matrix(_, _), mut b @ Type::List(_))
+            | (mut a @ Type::List(_), mut b @ Type::Matrix(_, _)) => {
+                // Broadcast the list over rows of the matrix
+                match (a.get_array(), b.get_array()) {
+                    (Some(a), Some(b)) => match a.zip_with(&b, op) {
+                        Some(array) if array.ndim() == 2 => {
+                            Type::Matrix(array.elements(), (array.shape()[0], array.shape()[1]))
+                        }
+                        Some(array) => Type::Array(array),
+                        None => {
+                            self.log_print("Error! shapes cannot be broadcast\n".to_string());
+                            Type::Error("shape-mismatch".to_string())
+                        }
+                    },
+                    _ => {
+                        self.log_print("Error! list is ragged to be array\n".to_string());
+                        Type::Error("ragged-array".to_string())
+                    }
+                }
+            }
+            (Type::Matrix(a, size), mut b) => {
+                let b = b.get_number();
+                Type::Matrix(a.into_iter().map(|x| op(x, b)).collect(), size)
+            }
+            (mut a, Type::Matrix(b, size)) => {
+                let a = a.get_number();
+                Type::Matrix(b.into_iter().map(|x| op(a, x)).collect(), size)
+            }
+            (Type::List(a), Type::List(b)) => {
+                if a.len() == b.len() {
+                    Type::List(
+                        a.into_iter()
+                            .zip(b)
+                            .map(|(x, y)| self.arithmetic(x, y, op))
+                            .collect(),
+                    )
+                } else {
+                    self.log_print("Error! lists are different length\n".to_string());
+                    Type::Error("length-mismatch".to_string())
+                }
+            }
+            (Type::List(a), b) => Type::List(
+                a.into_iter()
+                    .map(|x| self.arithmetic(x, b.clone(), op))
+                    .collect(),
+            ),
+            (a, Type::List(b)) => Type::List(
+                b.into_iter()
+                    .map(|x| self.arithmetic(a.clone(), x, op))
+                    .collect(),
+            ),
+            (mut a, mut b) => Type::Number(op(a.get_number(), b.get_number())),
         }
     }
 
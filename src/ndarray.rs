@@ -0,0 +1,353 @@
+use crate::{Fraction, Type};
+
+/// N-dimensional array of fractions
+#[derive(Clone, Debug)]
+pub struct NdArray {
+    data: Vec<Fraction>, // Element storage
+    shape: Vec<usize>,   // Length of each axis
+    strides: Vec<isize>, // Distance in storage between neighbors of each axis
+    offset: usize,       // Storage position of the first element
+}
+
+impl NdArray {
+    /// Constructor from row-major elements
+    pub fn new(data: Vec<Fraction>, shape: Vec<usize>) -> NdArray {
+        NdArray {
+            strides: NdArray::contiguous_strides(&shape),
+            data,
+            shape,
+            offset: 0,
+        }
+    }
+
+    /// Array with no axes holding a single value
+    pub fn scalar(value: Fraction) -> NdArray {
+        NdArray::new(vec![value], vec![])
+    }
+
+    /// Convert from matrix's data
+    pub fn from_matrix(data: Vec<Fraction>, (rows, cols): (usize, usize)) -> NdArray {
+        NdArray::new(data, vec![rows, cols])
+    }
+
+    /// Convert from nested list, fails if the list is ragged
+    pub fn from_list(list: &[Type]) -> Option<NdArray> {
+        let mut items = list.iter().map(|item| match item {
+            Type::List(inner) => NdArray::from_list(inner),
+            Type::Array(array) => Some(array.clone()),
+            other => Some(NdArray::scalar(other.clone().get_number())),
+        });
+
+        let first = match items.next() {
+            Some(item) => item?,
+            None => return Some(NdArray::new(vec![], vec![0])),
+        };
+        let mut data = first.elements();
+        for item in items {
+            let item = item?;
+            if item.shape != first.shape {
+                return None;
+            }
+            data.extend(item.elements());
+        }
+
+        let mut shape = vec![list.len()];
+        shape.extend(first.shape);
+        Some(NdArray::new(data, shape))
+    }
+
+    /// Row-major strides of the shape
+    fn contiguous_strides(shape: &[usize]) -> Vec<isize> {
+        let mut strides = vec![1; shape.len()];
+        for i in (0..shape.len().saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1] as isize;
+        }
+        strides
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn ndim(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// Count of elements
+    pub fn size(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Storage position of every element in row-major order
+    fn positions(&self) -> Vec<usize> {
+        let size = self.size();
+        let mut positions = Vec::with_capacity(size);
+        let mut index = vec![0; self.ndim()];
+
+        for _ in 0..size {
+            let position = self.offset as isize
+                + index
+                    .iter()
+                    .zip(&self.strides)
+                    .map(|(i, stride)| *i as isize * stride)
+                    .sum::<isize>();
+            positions.push(position as usize);
+
+            // Increment the index like an odometer
+            for axis in (0..self.ndim()).rev() {
+                index[axis] += 1;
+                if index[axis] < self.shape[axis] {
+                    break;
+                }
+                index[axis] = 0;
+            }
+        }
+        positions
+    }
+
+    /// Elements in row-major order
+    pub fn elements(&self) -> Vec<Fraction> {
+        self.positions().iter().map(|i| self.data[*i]).collect()
+    }
+
+    /// Apply function to every element
+    pub fn map(&self, f: impl Fn(Fraction) -> Fraction) -> NdArray {
+        NdArray::new(
+            self.elements().into_iter().map(f).collect(),
+            self.shape.clone(),
+        )
+    }
+
+    /// Shape that both shapes are broadcast to
+    fn broadcast_shape(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+        let ndim = a.len().max(b.len());
+        let mut shape = vec![0; ndim];
+        for i in 0..ndim {
+            let x = if i < ndim - a.len() {
+                1
+            } else {
+                a[i - (ndim - a.len())]
+            };
+            let y = if i < ndim - b.len() {
+                1
+            } else {
+                b[i - (ndim - b.len())]
+            };
+            shape[i] = match (x, y) {
+                (x, y) if x == y => x,
+                (1, y) => y,
+                (x, 1) => x,
+                _ => return None,
+            };
+        }
+        Some(shape)
+    }
+
+    /// View of the array repeated to the shape
+    fn broadcast_to(&self, shape: &[usize]) -> Option<NdArray> {
+        let lead = shape.len().checked_sub(self.ndim())?;
+        let mut strides = vec![0; shape.len()];
+        for axis in 0..self.ndim() {
+            if self.shape[axis] == shape[lead + axis] {
+                strides[lead + axis] = self.strides[axis];
+            } else if self.shape[axis] != 1 {
+                return None;
+            }
+        }
+
+        Some(NdArray {
+            data: self.data.clone(),
+            shape: shape.to_vec(),
+            strides,
+            offset: self.offset,
+        })
+    }
+
+    /// Combine element-wise with broadcasting
+    pub fn zip_with(
+        &self,
+        other: &NdArray,
+        f: impl Fn(Fraction, Fraction) -> Fraction,
+    ) -> Option<NdArray> {
+        let shape = NdArray::broadcast_shape(&self.shape, &other.shape)?;
+        let a = self.broadcast_to(&shape)?.elements();
+        let b = other.broadcast_to(&shape)?.elements();
+        Some(NdArray::new(
+            a.into_iter().zip(b).map(|(x, y)| f(x, y)).collect(),
+            shape,
+        ))
+    }
+
+    /// Change shape keeping elements, a negative length is inferred
+    pub fn reshape(&self, shape: &[isize]) -> Option<NdArray> {
+        let known: usize = shape
+            .iter()
+            .filter(|x| **x >= 0)
+            .map(|x| *x as usize)
+            .product();
+        let inferred = shape.iter().filter(|x| **x < 0).count();
+
+        let shape: Vec<usize> = match inferred {
+            0 => shape.iter().map(|x| *x as usize).collect(),
+            1 if known != 0 && self.size().is_multiple_of(known) => shape
+                .iter()
+                .map(|x| {
+                    if *x < 0 {
+                        self.size() / known
+                    } else {
+                        *x as usize
+                    }
+                })
+                .collect(),
+            _ => return None,
+        };
+
+        if shape.iter().product::<usize>() == self.size() {
+            Some(NdArray::new(self.elements(), shape))
+        } else {
+            None
+        }
+    }
+
+    /// Reorder axes by permutation
+    pub fn permute(&self, axes: &[usize]) -> Option<NdArray> {
+        let mut sorted = axes.to_vec();
+        sorted.sort();
+        if sorted != (0..self.ndim()).collect::<Vec<usize>>() {
+            return None;
+        }
+
+        Some(NdArray {
+            data: self.data.clone(),
+            shape: axes.iter().map(|axis| self.shape[*axis]).collect(),
+            strides: axes.iter().map(|axis| self.strides[*axis]).collect(),
+            offset: self.offset,
+        })
+    }
+
+    /// Reverse order of axes
+    pub fn transpose(&self) -> NdArray {
+        let axes: Vec<usize> = (0..self.ndim()).rev().collect();
+        self.permute(&axes).unwrap_or(self.clone())
+    }
+
+    /// Select by slicing specification like `0, 1:3, ::-1`
+    pub fn slice(&self, spec: &str) -> Option<NdArray> {
+        let entries: Vec<&str> = if spec.trim().is_empty() {
+            vec![]
+        } else {
+            spec.split(',').map(|x| x.trim()).collect()
+        };
+        if entries.len() > self.ndim() {
+            return None;
+        }
+
+        let mut offset = self.offset as isize;
+        let mut shape = Vec::new();
+        let mut strides = Vec::new();
+
+        for axis in 0..self.ndim() {
+            let length = self.shape[axis] as isize;
+            let stride = self.strides[axis];
+            let entry = entries.get(axis).copied().unwrap_or(":");
+
+            if !entry.contains(':') {
+                // Single index removes the axis
+                let mut index = entry.parse::<isize>().ok()?;
+                if index < 0 {
+                    index += length;
+                }
+                if index < 0 || index >= length {
+                    return None;
+                }
+                offset += index * stride;
+                continue;
+            }
+
+            let parts: Vec<&str> = entry.split(':').map(|x| x.trim()).collect();
+            if parts.len() > 3 {
+                return None;
+            }
+            let parse = |text: Option<&&str>| -> Option<Option<isize>> {
+                match text {
+                    Some(text) if !text.is_empty() => text.parse::<isize>().ok().map(Some),
+                    _ => Some(None),
+                }
+            };
+            let step = parse(parts.get(2))?.unwrap_or(1);
+            if step == 0 {
+                return None;
+            }
+            let bound = |value: isize, low: isize, high: isize| {
+                let value = if value < 0 { value + length } else { value };
+                value.clamp(low, high)
+            };
+
+            let (start, count) = if step > 0 {
+                let start = parse(parts.first())?.map_or(0, |x| bound(x, 0, length));
+                let stop = parse(parts.get(1))?.map_or(length, |x| bound(x, 0, length));
+                (start, (stop - start + step - 1).div_euclid(step).max(0))
+            } else {
+                let start = parse(parts.first())?.map_or(length - 1, |x| bound(x, -1, length - 1));
+                let stop = parse(parts.get(1))?.map_or(-1, |x| bound(x, -1, length - 1));
+                (start, (start - stop - step - 1).div_euclid(-step).max(0))
+            };
+
+            if count > 0 {
+                offset += start * stride;
+            }
+            shape.push(count as usize);
+            strides.push(stride * step);
+        }
+
+        Some(NdArray {
+            data: self.data.clone(),
+            shape,
+            strides,
+            offset: offset as usize,
+        })
+    }
+
+    /// Fold lanes along the axis into single values
+    pub fn reduce(&self, axis: usize, f: impl Fn(&[Fraction]) -> Fraction) -> Option<NdArray> {
+        if axis >= self.ndim() || self.shape[axis] == 0 {
+            return None;
+        }
+
+        // Move the reduced axis last so that every lane is contiguous
+        let mut axes: Vec<usize> = (0..self.ndim()).filter(|x| *x != axis).collect();
+        axes.push(axis);
+        let elements = self.permute(&axes)?.elements();
+
+        let mut shape = self.shape.clone();
+        shape.remove(axis);
+        Some(NdArray::new(
+            elements.chunks(self.shape[axis]).map(f).collect(),
+            shape,
+        ))
+    }
+
+    /// Convert to nested list, an array without axes becomes a number
+    pub fn to_list(&self) -> Type {
+        fn build(elements: &[Fraction], shape: &[usize]) -> Type {
+            match shape.split_first() {
+                None => Type::Number(elements[0]),
+                Some((_, rest)) => Type::List(
+                    elements
+                        .chunks(rest.iter().product::<usize>())
+                        .map(|x| build(x, rest))
+                        .collect(),
+                ),
+            }
+        }
+        if self.size() == 0 {
+            return Type::List(vec![]);
+        }
+        build(&self.elements(), &self.shape)
+    }
+
+    /// Show data to display
+    pub fn display(&self) -> String {
+        format!("array:{}", self.to_list().display())
+    }
+}
@@ -0,0 +1,207 @@
+/// Right-hand side of dy/dt = f(t, y)
+pub type System<'a> = dyn FnMut(f64, &[f64]) -> Vec<f64> + 'a;
+
+/// Times and states of the solution
+pub type Solution = (Vec<f64>, Vec<Vec<f64>>);
+
+/// Reason why the solution is not found
+pub enum Failure {
+    LengthMismatch, // The right-hand side returns other components than the state
+    NoConvergence,
+}
+
+/// One step of a method from the time and state by the step size
+type Step = fn(&mut System, f64, &[f64], f64) -> Result<Vec<f64>, Failure>;
+
+/// y + h * Σ c_i k_i
+fn combine(y: &[f64], h: f64, terms: &[(f64, &[f64])]) -> Vec<f64> {
+    y.iter()
+        .enumerate()
+        .map(|(i, y)| y + h * terms.iter().map(|(c, k)| c * k[i]).sum::<f64>())
+        .collect()
+}
+
+/// Check every value is inside range of fractions, which saturate beyond it
+fn in_range(y: Vec<f64>) -> Result<Vec<f64>, Failure> {
+    if y.iter().all(|x| x.abs() < isize::MAX as f64) {
+        Ok(y)
+    } else {
+        Err(Failure::NoConvergence)
+    }
+}
+
+/// Evaluate the right-hand side, checking its length against the state before use,
+/// as well as range of derivatives since saturated ones keep the state in range
+fn evaluate(f: &mut System, t: f64, y: &[f64]) -> Result<Vec<f64>, Failure> {
+    let k = f(t, y);
+    if k.len() != y.len() {
+        return Err(Failure::LengthMismatch);
+    }
+    in_range(k)
+}
+
+/// Integrate by a one-step method with fixed step size
+fn fixed_step(
+    f: &mut System,
+    (t0, t1, h): (f64, f64, f64),
+    y0: &[f64],
+    step: Step,
+) -> Result<Solution, Failure> {
+    let count = ((t1 - t0) / h).abs().ceil().max(1.0) as usize;
+    let h = (t1 - t0) / count as f64;
+
+    let mut times = vec![t0];
+    let mut states = vec![y0.to_vec()];
+    for i in 0..count {
+        let y = in_range(step(f, t0 + i as f64 * h, &states[i], h)?)?;
+        times.push(t0 + (i + 1) as f64 * h);
+        states.push(y);
+    }
+    Ok((times, states))
+}
+
+/// Forward Euler method
+pub fn euler(f: &mut System, span: (f64, f64, f64), y0: &[f64]) -> Result<Solution, Failure> {
+    fixed_step(f, span, y0, |f, t, y, h| {
+        Ok(combine(y, h, &[(1.0, &evaluate(f, t, y)?)]))
+    })
+}
+
+/// Classical fourth order Runge-Kutta method
+pub fn rk4(f: &mut System, span: (f64, f64, f64), y0: &[f64]) -> Result<Solution, Failure> {
+    fixed_step(f, span, y0, |f, t, y, h| {
+        let k1 = evaluate(f, t, y)?;
+        let k2 = evaluate(f, t + h / 2.0, &combine(y, h / 2.0, &[(1.0, &k1)]))?;
+        let k3 = evaluate(f, t + h / 2.0, &combine(y, h / 2.0, &[(1.0, &k2)]))?;
+        let k4 = evaluate(f, t + h, &combine(y, h, &[(1.0, &k3)]))?;
+        Ok(combine(
+            y,
+            h / 6.0,
+            &[(1.0, &k1), (2.0, &k2), (2.0, &k3), (1.0, &k4)],
+        ))
+    })
+}
+
+/// Adaptive Dormand-Prince method of order 5(4)
+pub fn dormand_prince(
+    f: &mut System,
+    span: (f64, f64, f64),
+    y0: &[f64],
+) -> Result<Solution, Failure> {
+    const TOLERANCE: f64 = 1e-6; // Absolute and relative tolerance
+    const MAX_STEPS: usize = 100_000;
+
+    let (t0, t1, h0) = span;
+    let direction = (t1 - t0).signum();
+    let mut h = h0.abs().max(1e-12) * direction;
+
+    let mut times = vec![t0];
+    let mut states = vec![y0.to_vec()];
+    let (mut t, mut y) = (t0, y0.to_vec());
+    let mut k1 = evaluate(f, t, &y)?;
+
+    for _ in 0..MAX_STEPS {
+        if (t1 - t) * direction <= 0.0 {
+            return Ok((times, states));
+        }
+        if (t + h - t1) * direction > 0.0 {
+            h = t1 - t;
+        }
+
+        let k2 = evaluate(f, t + h / 5.0, &combine(&y, h, &[(1.0 / 5.0, &k1)]))?;
+        let k3 = evaluate(
+            f,
+            t + h * 3.0 / 10.0,
+            &combine(&y, h, &[(3.0 / 40.0, &k1), (9.0 / 40.0, &k2)]),
+        )?;
+        let k4 = evaluate(
+            f,
+            t + h * 4.0 / 5.0,
+            &combine(
+                &y,
+                h,
+                &[(44.0 / 45.0, &k1), (-56.0 / 15.0, &k2), (32.0 / 9.0, &k3)],
+            ),
+        )?;
+        let k5 = evaluate(
+            f,
+            t + h * 8.0 / 9.0,
+            &combine(
+                &y,
+                h,
+                &[
+                    (19372.0 / 6561.0, &k1),
+                    (-25360.0 / 2187.0, &k2),
+                    (64448.0 / 6561.0, &k3),
+                    (-212.0 / 729.0, &k4),
+                ],
+            ),
+        )?;
+        let k6 = evaluate(
+            f,
+            t + h,
+            &combine(
+                &y,
+                h,
+                &[
+                    (9017.0 / 3168.0, &k1),
+                    (-355.0 / 33.0, &k2),
+                    (46732.0 / 5247.0, &k3),
+                    (49.0 / 176.0, &k4),
+                    (-5103.0 / 18656.0, &k5),
+                ],
+            ),
+        )?;
+        let next = combine(
+            &y,
+            h,
+            &[
+                (35.0 / 384.0, &k1),
+                (500.0 / 1113.0, &k3),
+                (125.0 / 192.0, &k4),
+                (-2187.0 / 6784.0, &k5),
+                (11.0 / 84.0, &k6),
+            ],
+        );
+        let next = in_range(next)?;
+        let k7 = evaluate(f, t + h, &next)?;
+
+        // Difference between fifth and fourth order solutions
+        let difference = combine(
+            &vec![0.0; y.len()],
+            h,
+            &[
+                (71.0 / 57600.0, &k1),
+                (-71.0 / 16695.0, &k3),
+                (71.0 / 1920.0, &k4),
+                (-17253.0 / 339200.0, &k5),
+                (22.0 / 525.0, &k6),
+                (-1.0 / 40.0, &k7),
+            ],
+        );
+        let error = difference
+            .iter()
+            .enumerate()
+            .map(|(i, e)| e.abs() / (TOLERANCE * (1.0 + y[i].abs().max(next[i].abs()))))
+            .fold(0.0, f64::max);
+
+        if error <= 1.0 {
+            t += h;
+            y = next;
+            k1 = k7;
+            times.push(t);
+            states.push(y.clone());
+        }
+
+        let factor = if error == 0.0 {
+            5.0
+        } else {
+            (0.9 * error.powf(-0.2)).clamp(0.2, 5.0)
+        };
+        h *= factor;
+        if h.abs() <= 1e-12 * t.abs().max(1.0) {
+            return Err(Failure::NoConvergence);
+        }
+    }
+    Err(Failure::NoConvergence)
+}
@@ -0,0 +1,357 @@
+use crate::calculus;
+
+/// Maximum count of iterations
+const MAX_ITERATIONS: usize = 1000;
+
+/// Relative tolerance of the position
+const TOLERANCE: f64 = 1e-8;
+
+/// Result of minimization
+pub struct Minimum {
+    pub argmin: Vec<f64>,
+    pub value: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+/// Project the point into the box of bounds
+pub fn clamp(x: &[f64], bounds: &[(f64, f64)]) -> Vec<f64> {
+    x.iter()
+        .enumerate()
+        .map(|(i, x)| match bounds.get(i) {
+            Some((low, high)) => x.clamp(*low, *high),
+            None => *x,
+        })
+        .collect()
+}
+
+/// Golden-section search on the interval
+pub fn golden_section(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64) -> Minimum {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = (a.min(b), a.max(b));
+    let mut c = b - ratio * (b - a);
+    let mut d = a + ratio * (b - a);
+    let (mut fc, mut fd) = (f(c), f(d));
+
+    for i in 1..=MAX_ITERATIONS {
+        if (b - a).abs() <= TOLERANCE * (c.abs() + d.abs()).max(1.0) {
+            let x = (a + b) / 2.0;
+            return Minimum {
+                argmin: vec![x],
+                value: f(x),
+                iterations: i,
+                converged: true,
+            };
+        }
+        if fc < fd {
+            (b, d, fd) = (d, c, fc);
+            c = b - ratio * (b - a);
+            fc = f(c);
+        } else {
+            (a, c, fc) = (c, d, fd);
+            d = a + ratio * (b - a);
+            fd = f(d);
+        }
+    }
+
+    let x = (a + b) / 2.0;
+    Minimum {
+        argmin: vec![x],
+        value: f(x),
+        iterations: MAX_ITERATIONS,
+        converged: false,
+    }
+}
+
+/// Brent's method combining golden-section search and parabolic interpolation
+pub fn brent(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64, start: f64) -> Minimum {
+    let golden = (3.0 - 5f64.sqrt()) / 2.0;
+    let (mut a, mut b) = (a.min(b), a.max(b));
+    let mut x = if start > a && start < b {
+        start
+    } else {
+        a + golden * (b - a)
+    };
+    let (mut w, mut v) = (x, x);
+    let mut fx = f(x);
+    let (mut fw, mut fv) = (fx, fx);
+    let (mut d, mut e): (f64, f64) = (0.0, 0.0);
+
+    for i in 1..=MAX_ITERATIONS {
+        let middle = (a + b) / 2.0;
+        let tolerance = TOLERANCE * x.abs() + 1e-10;
+        if (x - middle).abs() <= 2.0 * tolerance - (b - a) / 2.0 {
+            return Minimum {
+                argmin: vec![x],
+                value: fx,
+                iterations: i,
+                converged: true,
+            };
+        }
+
+        let mut golden_step = true;
+        if e.abs() > tolerance {
+            // Fit parabola through x, w and v
+            let r = (x - w) * (fx - fv);
+            let mut q = (x - v) * (fx - fw);
+            let mut p = (x - v) * q - (x - w) * r;
+            q = 2.0 * (q - r);
+            if q > 0.0 {
+                p = -p;
+            }
+            q = q.abs();
+            if p.abs() < (0.5 * q * e).abs() && p > q * (a - x) && p < q * (b - x) {
+                e = d;
+                d = p / q;
+                let u = x + d;
+                if u - a < 2.0 * tolerance || b - u < 2.0 * tolerance {
+                    d = tolerance.copysign(middle - x);
+                }
+                golden_step = false;
+            }
+        }
+        if golden_step {
+            e = if x >= middle { a - x } else { b - x };
+            d = golden * e;
+        }
+
+        let u = if d.abs() >= tolerance {
+            x + d
+        } else {
+            x + tolerance.copysign(d)
+        };
+        let fu = f(u);
+
+        if fu <= fx {
+            if u >= x {
+                a = x;
+            } else {
+                b = x;
+            }
+            (v, fv) = (w, fw);
+            (w, fw) = (x, fx);
+            (x, fx) = (u, fu);
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                (v, fv) = (w, fw);
+                (w, fw) = (u, fu);
+            } else if fu <= fv || v == x || v == w {
+                (v, fv) = (u, fu);
+            }
+        }
+    }
+
+    Minimum {
+        argmin: vec![x],
+        value: fx,
+        iterations: MAX_ITERATIONS,
+        converged: false,
+    }
+}
+
+/// Nelder-Mead simplex method
+pub fn nelder_mead(f: &mut dyn FnMut(&[f64]) -> f64, start: &[f64]) -> Minimum {
+    let n = start.len();
+
+    // Initial simplex stretched along each axis
+    let mut simplex: Vec<(Vec<f64>, f64)> = vec![(start.to_vec(), f(start))];
+    for i in 0..n {
+        let mut point = start.to_vec();
+        point[i] += if point[i] == 0.0 {
+            0.00025
+        } else {
+            0.05 * point[i]
+        };
+        let value = f(&point);
+        simplex.push((point, value));
+    }
+
+    // x_o + t (x_o - x_worst)
+    let along = |centroid: &[f64], worst: &[f64], t: f64| -> Vec<f64> {
+        centroid
+            .iter()
+            .zip(worst)
+            .map(|(c, w)| c + t * (c - w))
+            .collect()
+    };
+
+    for i in 1..=MAX_ITERATIONS * n.max(1) {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let spread = (simplex[n].1 - simplex[0].1).abs();
+        let size = simplex
+            .iter()
+            .flat_map(|(x, _)| x.iter().zip(&simplex[0].0).map(|(a, b)| (a - b).abs()))
+            .fold(0.0, f64::max);
+        let scale = simplex[0]
+            .0
+            .iter()
+            .fold(1.0, |acc: f64, x| acc.max(x.abs()));
+        if spread <= TOLERANCE * simplex[0].1.abs().max(1.0) && size <= 1e-6 * scale {
+            return Minimum {
+                argmin: simplex[0].0.clone(),
+                value: simplex[0].1,
+                iterations: i,
+                converged: true,
+            };
+        }
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f64>() / n as f64)
+            .collect();
+        let worst = simplex[n].clone();
+
+        let reflected = along(&centroid, &worst.0, 1.0);
+        let reflected_value = f(&reflected);
+        if reflected_value < simplex[0].1 {
+            let expanded = along(&centroid, &worst.0, 2.0);
+            let expanded_value = f(&expanded);
+            simplex[n] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+        } else if reflected_value < simplex[n - 1].1 {
+            simplex[n] = (reflected, reflected_value);
+        } else {
+            let contracted = if reflected_value < worst.1 {
+                along(&centroid, &worst.0, 0.5)
+            } else {
+                along(&centroid, &worst.0, -0.5)
+            };
+            let contracted_value = f(&contracted);
+            if contracted_value < worst.1.min(reflected_value) {
+                simplex[n] = (contracted, contracted_value);
+            } else {
+                // Shrink toward the best point
+                let best = simplex[0].0.clone();
+                for (point, value) in simplex.iter_mut().skip(1) {
+                    *point = point
+                        .iter()
+                        .zip(&best)
+                        .map(|(x, b)| b + 0.5 * (x - b))
+                        .collect();
+                    *value = f(point);
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    Minimum {
+        argmin: simplex[0].0.clone(),
+        value: simplex[0].1,
+        iterations: MAX_ITERATIONS * n.max(1),
+        converged: false,
+    }
+}
+
+/// Gradient by numerical partial derivatives
+fn gradient(f: &mut dyn FnMut(&[f64]) -> f64, x: &[f64]) -> Vec<f64> {
+    (0..x.len())
+        .map(|i| {
+            let (value, _) = calculus::derivative(
+                |t| {
+                    let mut args = x.to_vec();
+                    args[i] = t;
+                    vec![f(&args)]
+                },
+                x[i],
+                1,
+            );
+            value[0]
+        })
+        .collect()
+}
+
+/// Quasi-Newton method of Broyden-Fletcher-Goldfarb-Shanno with numerical gradient
+pub fn bfgs(f: &mut dyn FnMut(&[f64]) -> f64, start: &[f64]) -> Minimum {
+    let n = start.len();
+    let mut x = start.to_vec();
+    let mut fx = f(&x);
+    let mut g = gradient(f, &x);
+    let mut inverse = nalgebra::DMatrix::<f64>::identity(n, n); // Approximate inverse hessian
+
+    for i in 1..=MAX_ITERATIONS {
+        let norm = g.iter().fold(0.0, |acc: f64, x| acc.max(x.abs()));
+        if norm <= 1e-6 * fx.abs().max(1.0) {
+            return Minimum {
+                argmin: x,
+                value: fx,
+                iterations: i,
+                converged: true,
+            };
+        }
+
+        let gradient_vector = nalgebra::DVector::from_vec(g.clone());
+        let mut direction = -(&inverse * &gradient_vector);
+        if direction.dot(&gradient_vector) >= 0.0 {
+            // Not a descent direction, restart from steepest descent
+            inverse = nalgebra::DMatrix::identity(n, n);
+            direction = -gradient_vector.clone();
+        }
+
+        // Backtracking line search with Armijo condition
+        let slope = direction.dot(&gradient_vector);
+        let mut alpha = 1.0;
+        let (next, next_value) = loop {
+            let next: Vec<f64> = x
+                .iter()
+                .zip(direction.iter())
+                .map(|(x, d)| x + alpha * d)
+                .collect();
+            let value = f(&next);
+            if value <= fx + 1e-4 * alpha * slope {
+                break (next, value);
+            }
+            alpha /= 2.0;
+            if alpha < 1e-12 {
+                // No decrease is left within resolution of fractions,
+                // which also limits the accuracy of numerical gradient
+                return Minimum {
+                    argmin: x,
+                    value: fx,
+                    iterations: i,
+                    converged: norm <= 1e-3 * fx.abs().max(1.0),
+                };
+            }
+        };
+
+        let next_gradient = gradient(f, &next);
+        let s = nalgebra::DVector::from_iterator(n, next.iter().zip(&x).map(|(a, b)| a - b));
+        let y =
+            nalgebra::DVector::from_iterator(n, next_gradient.iter().zip(&g).map(|(a, b)| a - b));
+        let scale = y.dot(&s);
+        if scale > 1e-12 {
+            // Update of the inverse hessian
+            let rho = 1.0 / scale;
+            let identity = nalgebra::DMatrix::<f64>::identity(n, n);
+            let left = &identity - rho * &s * y.transpose();
+            let right = &identity - rho * &y * s.transpose();
+            inverse = left * &inverse * right + rho * &s * s.transpose();
+        }
+
+        let step = s.amax();
+        (x, fx, g) = (next, next_value, next_gradient);
+        if step <= TOLERANCE * x.iter().fold(1.0, |acc: f64, x| acc.max(x.abs())) {
+            return Minimum {
+                argmin: x,
+                value: fx,
+                iterations: i,
+                converged: true,
+            };
+        }
+    }
+
+    Minimum {
+        argmin: x,
+        value: fx,
+        iterations: MAX_ITERATIONS,
+        converged: false,
+    }
+}
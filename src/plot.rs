@@ -0,0 +1,206 @@
+use gnuplot::{AutoOption, AxesCommon, Caption, Figure, PlotOption};
+use std::path::Path;
+
+/// Style of drawing series
+#[derive(Clone, Copy, PartialEq)]
+enum Style {
+    Lines,
+    Points,
+    LinesPoints,
+    Steps,
+    Boxes,
+}
+
+/// Series of points with optional error bars of y
+pub struct Series {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    errors: Option<Vec<f64>>,
+    style: Style,
+    legend: Option<String>,
+}
+
+/// Split option like "xrange 0 10" into the key and the rest
+fn split_option(option: &str) -> (&str, &str) {
+    let option = option.trim();
+    match option.split_once(char::is_whitespace) {
+        Some((key, value)) => (key, value.trim()),
+        None => (option, ""),
+    }
+}
+
+/// Bound of range, where * lets it scale automatically
+fn parse_bound(bound: &str) -> Result<AutoOption<f64>, String> {
+    if bound == "*" {
+        return Ok(AutoOption::Auto);
+    }
+    bound
+        .parse::<f64>()
+        .map(AutoOption::Fix)
+        .map_err(|_| format!("bound {bound:?} is not a number"))
+}
+
+fn parse_range(range: &str) -> Result<(AutoOption<f64>, AutoOption<f64>), String> {
+    match range.split_whitespace().collect::<Vec<&str>>()[..] {
+        [min, max] => Ok((parse_bound(min)?, parse_bound(max)?)),
+        _ => Err(format!("range {range:?} needs minimum and maximum")),
+    }
+}
+
+impl Series {
+    /// Constructor from y, x and y, or x, y and errors of y,
+    /// and options of style (lines, points, scatter, linespoints, steps, boxes) and legend
+    pub fn new(mut lists: Vec<Vec<f64>>, options: &[String]) -> Result<Series, String> {
+        let errors = match lists.len() {
+            1 => {
+                let x = (1..=lists[0].len()).map(|i| i as f64).collect();
+                lists.insert(0, x);
+                None
+            }
+            2 => None,
+            3 => lists.pop(),
+            n => return Err(format!("series has {n} lists of numbers")),
+        };
+        let y = lists.pop().unwrap_or_default();
+        let x = lists.pop().unwrap_or_default();
+        if x.len() != y.len() || errors.as_ref().is_some_and(|e| e.len() != y.len()) {
+            return Err("lengths of lists in series are different".to_string());
+        }
+
+        let mut series = Series {
+            x,
+            y,
+            errors,
+            style: Style::Lines,
+            legend: None,
+        };
+        for option in options {
+            match split_option(option) {
+                ("style", style) => {
+                    series.style = match style {
+                        "lines" => Style::Lines,
+                        "points" | "scatter" => Style::Points,
+                        "linespoints" => Style::LinesPoints,
+                        "steps" => Style::Steps,
+                        "boxes" => Style::Boxes,
+                        _ => return Err(format!("unknown style {style:?}")),
+                    }
+                }
+                ("legend", legend) => series.legend = Some(legend.to_string()),
+                _ => return Err(format!("unknown option of series {option:?}")),
+            }
+        }
+        Ok(series)
+    }
+
+    /// Points of staircase which keeps each value until the next x
+    fn steps(&self) -> (Vec<f64>, Vec<f64>) {
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+        for i in 0..self.x.len() {
+            if i > 0 {
+                x.push(self.x[i]);
+                y.push(self.y[i - 1]);
+            }
+            x.push(self.x[i]);
+            y.push(self.y[i]);
+        }
+        (x, y)
+    }
+}
+
+/// Options of the whole plot
+#[derive(Default)]
+pub struct Options {
+    title: Option<String>,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    log_x: bool,
+    log_y: bool,
+    x_range: Option<(AutoOption<f64>, AutoOption<f64>)>,
+    y_range: Option<(AutoOption<f64>, AutoOption<f64>)>,
+    grid: bool,
+    pub output: Option<String>,
+}
+
+impl Options {
+    /// Parse options of title, xlabel, ylabel, logx, logy, xrange, yrange, grid and output
+    pub fn parse(options: &[String]) -> Result<Options, String> {
+        let mut parsed = Options::default();
+        for option in options {
+            match split_option(option) {
+                ("title", title) => parsed.title = Some(title.to_string()),
+                ("xlabel", label) => parsed.x_label = Some(label.to_string()),
+                ("ylabel", label) => parsed.y_label = Some(label.to_string()),
+                ("logx", "") => parsed.log_x = true,
+                ("logy", "") => parsed.log_y = true,
+                ("xrange", range) => parsed.x_range = Some(parse_range(range)?),
+                ("yrange", range) => parsed.y_range = Some(parse_range(range)?),
+                ("grid", "") => parsed.grid = true,
+                ("output", path) => {
+                    let extension = Path::new(path)
+                        .extension()
+                        .and_then(|x| x.to_str())
+                        .map(|x| x.to_lowercase());
+                    match extension.as_deref() {
+                        Some("png" | "svg" | "pdf" | "gp") => {
+                            parsed.output = Some(path.to_string())
+                        }
+                        _ => return Err(format!("unknown format of output {path:?}")),
+                    }
+                }
+                _ => return Err(format!("unknown option of plot {option:?}")),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Figure of the series drawn on the same axes
+pub fn figure(series: &[Series], options: &Options) -> Figure {
+    let mut figure = Figure::new();
+    let axes = figure.axes2d();
+    for s in series {
+        let caption: Vec<PlotOption<&str>> = s.legend.iter().map(|x| Caption(x.as_str())).collect();
+        match (&s.errors, s.style) {
+            (Some(errors), Style::Points | Style::Boxes) => {
+                axes.y_error_bars(&s.x, &s.y, errors, &caption)
+            }
+            (Some(errors), _) => axes.y_error_lines(&s.x, &s.y, errors, &caption),
+            (None, Style::Lines) => axes.lines(&s.x, &s.y, &caption),
+            (None, Style::Points) => axes.points(&s.x, &s.y, &caption),
+            (None, Style::LinesPoints) => axes.lines_points(&s.x, &s.y, &caption),
+            (None, Style::Steps) => {
+                let (x, y) = s.steps();
+                axes.lines(x, y, &caption)
+            }
+            (None, Style::Boxes) => axes.boxes(&s.x, &s.y, &caption),
+        };
+    }
+
+    if let Some(title) = &options.title {
+        axes.set_title(title, &[]);
+    }
+    if let Some(label) = &options.x_label {
+        axes.set_x_label(label, &[]);
+    }
+    if let Some(label) = &options.y_label {
+        axes.set_y_label(label, &[]);
+    }
+    if options.log_x {
+        axes.set_x_log(Some(10.0));
+    }
+    if options.log_y {
+        axes.set_y_log(Some(10.0));
+    }
+    if let Some((min, max)) = options.x_range {
+        axes.set_x_range(min, max);
+    }
+    if let Some((min, max)) = options.y_range {
+        axes.set_y_range(min, max);
+    }
+    if options.grid {
+        axes.set_x_grid(true).set_y_grid(true);
+    }
+    figure
+}
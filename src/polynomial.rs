@@ -0,0 +1,308 @@
+use crate::{Fraction, Type};
+use std::ops::{Add, Mul, Sub};
+
+/// Polynomial of one variable with fractional coefficients
+#[derive(Clone, Debug)]
+pub struct Polynomial {
+    coefficients: Vec<Fraction>, // Coefficient of each power in ascending order, no zero at the end
+}
+
+impl Polynomial {
+    /// Constructor from coefficients of powers in ascending order
+    fn from_ascending(mut coefficients: Vec<Fraction>) -> Polynomial {
+        while coefficients.last().is_some_and(|x| x.numerator == 0) {
+            coefficients.pop();
+        }
+        Polynomial { coefficients }
+    }
+
+    /// Constructor from coefficients of the highest power first
+    pub fn new(mut coefficients: Vec<Fraction>) -> Polynomial {
+        coefficients.reverse();
+        Polynomial::from_ascending(coefficients)
+    }
+
+    /// Polynomial of degree zero
+    pub fn constant(value: Fraction) -> Polynomial {
+        Polynomial::from_ascending(vec![value])
+    }
+
+    /// Coefficients of the highest power first
+    pub fn coefficients(&self) -> Vec<Fraction> {
+        self.coefficients.iter().rev().copied().collect()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// Degree of the polynomial, the zero polynomial has none
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Value at the point by Horner's method
+    pub fn eval(&self, x: Fraction) -> Fraction {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Fraction::new(0.0), |acc, c| acc * x + *c)
+    }
+
+    /// Derivative
+    pub fn derivative(&self) -> Polynomial {
+        Polynomial::from_ascending(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| *c * Fraction::new(i as f64))
+                .collect(),
+        )
+    }
+
+    /// Antiderivative whose constant term is zero
+    pub fn integral(&self) -> Polynomial {
+        let mut coefficients = vec![Fraction::new(0.0)];
+        for (i, c) in self.coefficients.iter().enumerate() {
+            coefficients.push(*c / Fraction::new((i + 1) as f64));
+        }
+        Polynomial::from_ascending(coefficients)
+    }
+
+    /// Quotient and remainder of long division, fails for the zero divisor
+    pub fn div_rem(&self, divisor: &Polynomial) -> Option<(Polynomial, Polynomial)> {
+        let leading = *divisor.coefficients.last()?;
+        let mut remainder = self.coefficients.clone();
+        let count = (remainder.len() + 1).saturating_sub(divisor.coefficients.len());
+        let mut quotient = vec![Fraction::new(0.0); count];
+
+        for i in (0..count).rev() {
+            let factor = remainder[i + divisor.coefficients.len() - 1] / leading;
+            quotient[i] = factor;
+            for (j, c) in divisor.coefficients.iter().enumerate() {
+                remainder[i + j] = remainder[i + j] - factor * *c;
+            }
+            // Leading term cancels exactly
+            remainder.pop();
+        }
+        Some((
+            Polynomial::from_ascending(quotient),
+            Polynomial::from_ascending(remainder),
+        ))
+    }
+
+    /// Scale to make the leading coefficient one
+    pub fn monic(&self) -> Polynomial {
+        match self.coefficients.last() {
+            Some(leading) => Polynomial::from_ascending(
+                self.coefficients.iter().map(|c| *c / *leading).collect(),
+            ),
+            None => self.clone(),
+        }
+    }
+
+    /// Monic greatest common divisor by Euclid's algorithm
+    pub fn gcd(&self, other: &Polynomial) -> Polynomial {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while let Some((_, remainder)) = a.div_rem(&b) {
+            (a, b) = (b, remainder);
+        }
+        a.monic()
+    }
+
+    /// Is the value exactly a root, evaluated without approximation of fractions on overflow
+    fn is_root(&self, x: Fraction) -> bool {
+        fn gcd(mut a: i128, mut b: i128) -> i128 {
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a.abs().max(1)
+        }
+
+        let (xn, xd) = (x.numerator as i128, x.denominator as i128);
+        let mut value = Some((0i128, 1i128));
+        for c in self.coefficients.iter().rev() {
+            // value * x + c
+            value = value.and_then(|(n, d)| {
+                let (cn, cd) = (c.numerator as i128, c.denominator as i128);
+                let numerator = n
+                    .checked_mul(xn)?
+                    .checked_mul(cd)?
+                    .checked_add(cn.checked_mul(d)?.checked_mul(xd)?)?;
+                let denominator = d.checked_mul(xd)?.checked_mul(cd)?;
+                let divisor = gcd(numerator, denominator);
+                Some((numerator / divisor, denominator / divisor))
+            });
+        }
+        value.is_some_and(|(n, _)| n == 0)
+    }
+
+    /// Eigenvalues of the companion matrix as real and imaginary parts
+    fn eigenvalues(&self) -> Option<Vec<(f64, f64)>> {
+        let n = self.degree()?;
+        let leading = self.coefficients[n].to_f64();
+        let companion = nalgebra::DMatrix::<f64>::from_fn(n, n, |i, j| {
+            if j == n - 1 {
+                -self.coefficients[i].to_f64() / leading
+            } else if i == j + 1 {
+                1.0
+            } else {
+                0.0
+            }
+        });
+
+        // QR iteration may stall when roots have equal modulus as those of x^n - 1,
+        // shifting the matrix separates their moduli
+        [0.0, 0.1, -0.37].iter().find_map(|shift| {
+            let shifted = &companion + nalgebra::DMatrix::<f64>::identity(n, n) * *shift;
+            let schur = nalgebra::linalg::Schur::try_new(shifted, f64::EPSILON, 1000)?;
+            Some(
+                schur
+                    .complex_eigenvalues()
+                    .iter()
+                    .map(|z| (z.re - shift, z.im))
+                    .collect(),
+            )
+        })
+    }
+
+    /// Rational numbers near the value that may be a root,
+    /// whose denominator divides the leading coefficient of integral form
+    fn candidates(&self, value: f64) -> Vec<Fraction> {
+        const MAX_LEADING: i128 = 1_000_000_000_000;
+
+        let mut result = vec![Fraction::new(value)];
+
+        // Leading coefficient after clearing denominators
+        let mut multiple: i128 = 1;
+        for c in &self.coefficients {
+            let denominator = c.denominator as i128;
+            let (mut a, mut b) = (multiple, denominator);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            match (multiple / a).checked_mul(denominator) {
+                Some(lcm) if lcm <= MAX_LEADING => multiple = lcm,
+                _ => return result,
+            }
+        }
+        let leading = self.coefficients.last().map_or(1, |c| {
+            (c.numerator as i128 * (multiple / c.denominator as i128)).abs()
+        });
+        if leading > MAX_LEADING {
+            return result;
+        }
+
+        let mut q: i128 = 1;
+        while q * q <= leading {
+            if leading % q == 0 {
+                for denominator in [q, leading / q] {
+                    let numerator = (value * denominator as f64).round();
+                    if numerator.abs() < isize::MAX as f64 {
+                        result.push(Fraction::reduce(numerator as i128, denominator));
+                    }
+                }
+            }
+            q += 1;
+        }
+        result
+    }
+
+    /// Rational roots found exactly with multiplicity,
+    /// and the other roots approximately as real and imaginary parts
+    pub fn roots(&self) -> (Vec<Fraction>, Vec<(f64, f64)>) {
+        let mut exact = Vec::new();
+        let mut rest = self.clone();
+
+        // Roots at zero
+        while rest.degree().is_some_and(|n| n > 0) && rest.coefficients[0].numerator == 0 {
+            exact.push(Fraction::new(0.0));
+            rest.coefficients.remove(0);
+        }
+
+        while rest.degree().is_some_and(|n| n > 0) {
+            let eigenvalues = match rest.eigenvalues() {
+                Some(eigenvalues) => eigenvalues,
+                None => return (exact, vec![]),
+            };
+            // Multiple roots spread into the complex plane, so try every real part
+            let mut nearest = eigenvalues.clone();
+            nearest.sort_by(|a, b| a.1.abs().total_cmp(&b.1.abs()));
+            let root = nearest
+                .iter()
+                .flat_map(|(re, _)| rest.candidates(*re))
+                .find(|x| rest.is_root(*x));
+            match root {
+                Some(root) => {
+                    // Deflate by the factor x - root
+                    let factor = Polynomial::from_ascending(vec![
+                        Fraction::new(0.0) - root,
+                        Fraction::new(1.0),
+                    ]);
+                    exact.push(root);
+                    rest = rest.div_rem(&factor).map_or(rest, |(quotient, _)| quotient);
+                }
+                None => return (exact, eigenvalues),
+            }
+        }
+        (exact, vec![])
+    }
+
+    pub fn display(&self) -> String {
+        let coefficients = self.coefficients().into_iter().map(Type::Number).collect();
+        format!("poly:{}", Type::List(coefficients).display())
+    }
+}
+
+impl Polynomial {
+    /// Combine coefficients of each power, the missing ones are zero
+    fn zip_with(self, other: Polynomial, op: fn(Fraction, Fraction) -> Fraction) -> Polynomial {
+        let length = self.coefficients.len().max(other.coefficients.len());
+        let zero = Fraction::new(0.0);
+        Polynomial::from_ascending(
+            (0..length)
+                .map(|i| {
+                    op(
+                        *self.coefficients.get(i).unwrap_or(&zero),
+                        *other.coefficients.get(i).unwrap_or(&zero),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Add for Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: Polynomial) -> Polynomial {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl Sub for Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: Polynomial) -> Polynomial {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl Mul for Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: Polynomial) -> Polynomial {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::from_ascending(vec![]);
+        }
+        let mut coefficients =
+            vec![Fraction::new(0.0); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] = coefficients[i + j] + *a * *b;
+            }
+        }
+        Polynomial::from_ascending(coefficients)
+    }
+}
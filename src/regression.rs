@@ -0,0 +1,159 @@
+use crate::calculus;
+use nalgebra::{DMatrix, DVector};
+
+/// Maximum count of iterations
+const MAX_ITERATIONS: usize = 200;
+
+/// Slope, intercept and coefficient of determination of the least squares line
+pub fn linear(xs: &[f64], ys: &[f64]) -> Option<(f64, f64, f64)> {
+    let n = xs.len() as f64;
+    let (mean_x, mean_y) = (xs.iter().sum::<f64>() / n, ys.iter().sum::<f64>() / n);
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+        syy += (y - mean_y) * (y - mean_y);
+    }
+    if sxx == 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    // Constant data is fitted perfectly
+    let r2 = if syy == 0.0 {
+        1.0
+    } else {
+        sxy * sxy / (sxx * syy)
+    };
+    Some((slope, mean_y - slope * mean_x, r2))
+}
+
+/// Least squares solution of the overdetermined system by singular value decomposition
+pub fn least_squares(design: DMatrix<f64>, ys: &[f64]) -> Option<Vec<f64>> {
+    if design.is_empty() || design.nrows() != ys.len() {
+        return None;
+    }
+    let rhs = DVector::from_column_slice(ys);
+    let solution = design.svd(true, true).solve(&rhs, 1e-12).ok()?;
+    Some(solution.iter().copied().collect())
+}
+
+/// Coefficient of determination of the fitted values
+pub fn r_squared(ys: &[f64], fitted: &[f64]) -> f64 {
+    let mean = ys.iter().sum::<f64>() / ys.len() as f64;
+    let residual: f64 = ys.iter().zip(fitted).map(|(y, f)| (y - f).powi(2)).sum();
+    let total: f64 = ys.iter().map(|y| (y - mean).powi(2)).sum();
+    if total == 0.0 {
+        1.0
+    } else {
+        1.0 - residual / total
+    }
+}
+
+/// Coefficients of the least squares polynomial of the degree, the highest power first
+pub fn polyfit(xs: &[f64], ys: &[f64], degree: usize) -> Option<Vec<f64>> {
+    let vandermonde =
+        DMatrix::from_fn(xs.len(), degree + 1, |i, j| xs[i].powi((degree - j) as i32));
+    least_squares(vandermonde, ys)
+}
+
+/// Result of nonlinear fitting
+pub struct Fit {
+    pub parameters: Vec<f64>,
+    pub uncertainties: Vec<f64>,
+    pub residual: f64, // Sum of squared residuals
+    pub iterations: usize,
+}
+
+/// Levenberg-Marquardt method fitting the model of parameters to the data,
+/// where the model returns its values at every x
+pub fn levenberg_marquardt(
+    model: &mut dyn FnMut(&[f64]) -> Vec<f64>,
+    ys: &[f64],
+    start: &[f64],
+) -> Option<Fit> {
+    let (n, m) = (ys.len(), start.len());
+    let residuals = |values: &[f64]| -> Option<DVector<f64>> {
+        (values.len() == n && values.iter().all(|x| x.is_finite()))
+            .then(|| DVector::from_iterator(n, ys.iter().zip(values).map(|(y, f)| y - f)))
+    };
+
+    let mut parameters = start.to_vec();
+    let mut r = residuals(&model(&parameters))?;
+    let mut cost = r.norm_squared();
+    let mut lambda = 1e-3; // Damping factor
+    let mut jacobian = DMatrix::<f64>::zeros(n, m);
+
+    for i in 1..=MAX_ITERATIONS {
+        for j in 0..m {
+            let (column, _) = calculus::derivative(
+                |t| {
+                    let mut args = parameters.clone();
+                    args[j] = t;
+                    model(&args)
+                },
+                parameters[j],
+                1,
+            );
+            if column.len() != n {
+                return None;
+            }
+            for (k, value) in column.iter().enumerate() {
+                jacobian[(k, j)] = *value;
+            }
+        }
+        let normal = jacobian.transpose() * &jacobian;
+        let gradient = jacobian.transpose() * &r;
+
+        // Raise damping until the step decreases the cost
+        let accepted = loop {
+            let mut damped = normal.clone();
+            for j in 0..m {
+                damped[(j, j)] += lambda * normal[(j, j)].max(1e-12);
+            }
+            if let Some(step) = damped.lu().solve(&gradient) {
+                let trial: Vec<f64> = parameters
+                    .iter()
+                    .zip(step.iter())
+                    .map(|(p, d)| p + d)
+                    .collect();
+                if let Some(trial_r) = residuals(&model(&trial)) {
+                    let trial_cost = trial_r.norm_squared();
+                    if trial_cost <= cost {
+                        lambda = (lambda / 10.0).max(1e-12);
+                        break Some((trial, trial_r, trial_cost, step.amax()));
+                    }
+                }
+            }
+            lambda *= 10.0;
+            if lambda > 1e12 {
+                break None;
+            }
+        };
+
+        let converged = match accepted {
+            Some((trial, trial_r, trial_cost, step)) => {
+                let decrease = cost - trial_cost;
+                let scale = trial.iter().fold(1.0, |acc: f64, x| acc.max(x.abs()));
+                (parameters, r, cost) = (trial, trial_r, trial_cost);
+                decrease <= 1e-12 * cost.max(1e-12) || step <= 1e-10 * scale
+            }
+            // No step decreases the cost any more
+            None => true,
+        };
+
+        if converged {
+            // Covariance from the inverse of the normal matrix scaled by the residual variance
+            let variance = if n > m { cost / (n - m) as f64 } else { 0.0 };
+            let covariance = (jacobian.transpose() * &jacobian).try_inverse()?;
+            return Some(Fit {
+                uncertainties: (0..m)
+                    .map(|j| (covariance[(j, j)] * variance).max(0.0).sqrt())
+                    .collect(),
+                parameters,
+                residual: cost,
+                iterations: i,
+            });
+        }
+    }
+    None
+}
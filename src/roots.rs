@@ -0,0 +1,200 @@
+use crate::calculus;
+
+/// Maximum count of iterations
+const MAX_ITERATIONS: usize = 200;
+
+/// Is the step small enough relative to the position
+fn small_step(step: f64, x: f64) -> bool {
+    step.abs() <= 1e-10 * x.abs().max(1.0)
+}
+
+/// Bisection method on the bracket whose ends have opposite signs
+pub fn bisection(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64) -> Option<(f64, usize)> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some((a, 0));
+    }
+    if fb == 0.0 {
+        return Some((b, 0));
+    }
+    if fa * fb > 0.0 {
+        return None;
+    }
+
+    for i in 1..=MAX_ITERATIONS {
+        let middle = (a + b) / 2.0;
+        let fm = f(middle);
+        if fm == 0.0 || small_step(b - a, middle) {
+            return Some((middle, i));
+        }
+        if fa * fm < 0.0 {
+            b = middle;
+        } else {
+            (a, fa) = (middle, fm);
+        }
+    }
+    None
+}
+
+/// Newton's method with numerical derivative
+pub fn newton(f: &mut dyn FnMut(f64) -> f64, x0: f64) -> Option<(f64, usize)> {
+    let mut x = x0;
+    for i in 1..=MAX_ITERATIONS {
+        let fx = f(x);
+        if fx == 0.0 {
+            return Some((x, i - 1));
+        }
+        let (slope, _) = calculus::derivative(|x| vec![f(x)], x, 1);
+        let step = fx / slope[0];
+        if !step.is_finite() {
+            return None;
+        }
+        x -= step;
+        if small_step(step, x) {
+            return Some((x, i));
+        }
+    }
+    None
+}
+
+/// Secant method from two initial points
+pub fn secant(f: &mut dyn FnMut(f64) -> f64, x0: f64, x1: f64) -> Option<(f64, usize)> {
+    let (mut x0, mut x1) = (x0, x1);
+    let (mut f0, mut f1) = (f(x0), f(x1));
+    for i in 1..=MAX_ITERATIONS {
+        if f1 == 0.0 {
+            return Some((x1, i - 1));
+        }
+        let step = f1 * (x1 - x0) / (f1 - f0);
+        if !step.is_finite() {
+            return None;
+        }
+        (x0, f0) = (x1, f1);
+        x1 -= step;
+        f1 = f(x1);
+        if small_step(step, x1) {
+            return Some((x1, i));
+        }
+    }
+    None
+}
+
+/// Brent's method combining bisection, secant and inverse quadratic interpolation
+pub fn brent(f: &mut dyn FnMut(f64) -> f64, a: f64, b: f64) -> Option<(f64, usize)> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa * fb > 0.0 {
+        return None;
+    }
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+
+    for i in 1..=MAX_ITERATIONS {
+        // Keep the root between b and c
+        if fb * fc > 0.0 {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+        // b is the best estimate
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+
+        let tolerance = 2.0 * f64::EPSILON * b.abs() + 0.5e-12;
+        let middle = (c - b) / 2.0;
+        if middle.abs() <= tolerance || fb == 0.0 {
+            return Some((b, i));
+        }
+
+        if e.abs() >= tolerance && fa.abs() > fb.abs() {
+            // Try interpolation
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * middle * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * middle * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+
+            if 2.0 * p < (3.0 * middle * q - (tolerance * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = middle;
+                e = d;
+            }
+        } else {
+            d = middle;
+            e = d;
+        }
+
+        (a, fa) = (b, fb);
+        b += if d.abs() > tolerance {
+            d
+        } else {
+            tolerance.copysign(middle)
+        };
+        fb = f(b);
+    }
+    None
+}
+
+/// Newton's method for system of equations with numerical jacobian
+pub fn newton_system(
+    f: &mut dyn FnMut(&[f64]) -> Vec<f64>,
+    x0: &[f64],
+) -> Option<(Vec<f64>, usize)> {
+    let n = x0.len();
+    let mut x = x0.to_vec();
+
+    for i in 1..=MAX_ITERATIONS {
+        let fx = f(&x);
+        if fx.len() != n {
+            return None;
+        }
+        if fx.iter().all(|v| *v == 0.0) {
+            return Some((x, i - 1));
+        }
+
+        let mut jacobian = nalgebra::DMatrix::<f64>::zeros(n, n);
+        for j in 0..n {
+            let (column, _) = calculus::derivative(
+                |t| {
+                    let mut args = x.clone();
+                    args[j] = t;
+                    f(&args)
+                },
+                x[j],
+                1,
+            );
+            for (k, value) in column.iter().enumerate().take(n) {
+                jacobian[(k, j)] = *value;
+            }
+        }
+
+        let step = jacobian.lu().solve(&nalgebra::DVector::from_vec(fx))?;
+        if step.iter().any(|v| !v.is_finite()) {
+            return None;
+        }
+        for (xj, dj) in x.iter_mut().zip(step.iter()) {
+            *xj -= dj;
+        }
+        let scale = x.iter().fold(0.0, |acc: f64, v| acc.max(v.abs()));
+        if small_step(step.amax(), scale) {
+            return Some((x, i));
+        }
+    }
+    None
+}
@@ -0,0 +1,144 @@
+/// Relative tolerance of the limit
+const TOLERANCE: f64 = 1e-10;
+
+/// Transformation accelerating convergence of partial sums
+pub type Accelerate = fn(&[f64]) -> f64;
+
+/// Iterated Aitken's delta-squared process
+pub fn aitken(sums: &[f64]) -> f64 {
+    let mut row = sums.to_vec();
+    while row.len() >= 3 {
+        let next: Vec<f64> = row
+            .windows(3)
+            .map(|s| {
+                let denominator = s[2] - 2.0 * s[1] + s[0];
+                if denominator == 0.0 {
+                    s[2]
+                } else {
+                    s[2] - (s[2] - s[1]).powi(2) / denominator
+                }
+            })
+            .collect();
+        if next.iter().any(|x| !x.is_finite()) {
+            break;
+        }
+        row = next;
+    }
+    row[row.len() - 1]
+}
+
+/// Wynn's epsilon algorithm, the even columns of the table approximate the limit
+pub fn wynn(sums: &[f64]) -> f64 {
+    let mut previous = vec![0.0; sums.len() + 1];
+    let mut current = sums.to_vec();
+    let mut best = sums[sums.len() - 1];
+    for k in 1..sums.len() {
+        let next: Vec<f64> = (0..current.len() - 1)
+            .map(|n| previous[n + 1] + 1.0 / (current[n + 1] - current[n]))
+            .collect();
+        if next.iter().any(|x| !x.is_finite()) {
+            break;
+        }
+        if k % 2 == 0 {
+            best = next[next.len() - 1];
+        }
+        (previous, current) = (current, next);
+    }
+    best
+}
+
+/// Richardson extrapolation of partial sums of doubling count of terms,
+/// whose error is a series in the reciprocal of the count
+pub fn richardson(sums: &[f64]) -> f64 {
+    let mut row = sums.to_vec();
+    let mut factor = 2.0;
+    while row.len() >= 2 {
+        row = row
+            .windows(2)
+            .map(|s| (factor * s[1] - s[0]) / (factor - 1.0))
+            .collect();
+        factor *= 2.0;
+    }
+    row[0]
+}
+
+/// Sum of infinite series by accelerating partial sums,
+/// returns the sum and the estimated error
+pub fn infinite_sum(
+    term: &mut dyn FnMut(usize) -> f64,
+    accelerate: Accelerate,
+    doubling: bool,
+) -> Option<(f64, f64)> {
+    const MAX_TERMS: usize = 1 << 15;
+    const MAX_SAMPLES: usize = 300;
+
+    let mut sum = 0.0;
+    let mut samples: Vec<f64> = Vec::new();
+    let mut estimates: Vec<f64> = Vec::new();
+    for n in 0..MAX_TERMS {
+        let value = term(n);
+        if !value.is_finite() {
+            return None;
+        }
+        sum += value;
+
+        // Partial sums by every term, or by doubling count of terms
+        let count = n + 1;
+        if doubling && !(count >= 4 && count.is_power_of_two()) {
+            continue;
+        }
+        samples.push(sum);
+        if samples.len() < 3 {
+            continue;
+        }
+        if samples.len() > MAX_SAMPLES {
+            return None;
+        }
+
+        let estimate = accelerate(&samples);
+        estimates.push(estimate);
+
+        // Consecutive estimates agree twice, and with the estimate from half of the partial sums
+        // since estimates of divergent series may also settle on a short run
+        if let [.., a, b, c] = estimates[..] {
+            let half = estimates[(estimates.len() - 1) / 2];
+            let error = (c - b).abs().max((b - a).abs()).max((c - half).abs());
+            if estimates.len() >= 5 && error <= TOLERANCE * c.abs().max(1.0) {
+                return Some((c, error));
+            }
+        }
+    }
+    None
+}
+
+/// Limit of f(h) as h approaches zero by Richardson extrapolation of halving steps,
+/// whose error is a series in h, returns the limit and the estimated error
+pub fn limit(f: &mut dyn FnMut(f64) -> f64, step: f64) -> Option<(f64, f64)> {
+    const LEVELS: usize = 12;
+
+    let mut h = step;
+    let mut table: Vec<Vec<f64>> = Vec::new();
+    let mut best = None;
+    let mut error = f64::MAX;
+    for i in 0..LEVELS {
+        let value = f(h);
+        if !value.is_finite() {
+            break;
+        }
+        let mut row = vec![value];
+        let mut factor = 2.0;
+        for j in 1..=i {
+            let extrapolated = (factor * row[j - 1] - table[i - 1][j - 1]) / (factor - 1.0);
+            let trial = (extrapolated - row[j - 1]).abs();
+            if trial <= error {
+                error = trial;
+                best = Some(extrapolated);
+            }
+            row.push(extrapolated);
+            factor *= 2.0;
+        }
+        table.push(row);
+        h /= 2.0;
+    }
+    best.map(|value| (value, error))
+}
@@ -0,0 +1,209 @@
+use crate::Fraction;
+use std::cmp::Ordering;
+
+/// Exact order of fractions
+pub fn compare(a: &Fraction, b: &Fraction) -> Ordering {
+    let order = (a.numerator as i128 * b.denominator as i128)
+        .cmp(&(b.numerator as i128 * a.denominator as i128));
+    // Cross multiplication by a negative denominator flips the order
+    if (a.denominator < 0) != (b.denominator < 0) {
+        order.reverse()
+    } else {
+        order
+    }
+}
+
+/// Sorted copy of the data
+fn sorted(data: &[Fraction]) -> Vec<Fraction> {
+    let mut data = data.to_vec();
+    data.sort_by(compare);
+    data
+}
+
+/// Exact sum, or that in floating point if the exact one does not fit
+pub fn sum(data: &[Fraction]) -> Fraction {
+    let exact = data.iter().try_fold(Fraction::new(0.0), |acc, x| {
+        let numerator = (acc.numerator as i128 * x.denominator as i128)
+            .checked_add(x.numerator as i128 * acc.denominator as i128)?;
+        Fraction::exact(numerator, acc.denominator as i128 * x.denominator as i128)
+    });
+    exact.unwrap_or_else(|| Fraction::new(data.iter().map(|x| x.to_f64()).sum()))
+}
+
+pub fn mean(data: &[Fraction]) -> Option<Fraction> {
+    if data.is_empty() {
+        return None;
+    }
+    Some(sum(data) / Fraction::new(data.len() as f64))
+}
+
+pub fn median(data: &[Fraction]) -> Option<Fraction> {
+    quantile(data, Fraction::new(0.5))
+}
+
+/// Most frequent value, the smallest one among ties
+pub fn mode(data: &[Fraction]) -> Option<Fraction> {
+    let data = sorted(data);
+    let mut best: Option<(Fraction, usize)> = None;
+    let mut i = 0;
+    while i < data.len() {
+        let count = data[i..]
+            .iter()
+            .take_while(|x| compare(x, &data[i]) == Ordering::Equal)
+            .count();
+        if best.is_none_or(|(_, most)| count > most) {
+            best = Some((data[i], count));
+        }
+        i += count;
+    }
+    best.map(|(value, _)| value)
+}
+
+/// Central moment of the order
+fn moment(data: &[Fraction], order: usize) -> Option<Fraction> {
+    let mean = mean(data)?;
+    let powers: Vec<Fraction> = data
+        .iter()
+        .map(|x| (0..order).fold(Fraction::new(1.0), |acc, _| acc * (*x - mean)))
+        .collect();
+    Some(sum(&powers) / Fraction::new(data.len() as f64))
+}
+
+/// Variance, unbiased one of sample or that of population
+pub fn variance(data: &[Fraction], sample: bool) -> Option<Fraction> {
+    covariance(data, data, sample)
+}
+
+/// Standard deviation, of sample or of population
+pub fn stdev(data: &[Fraction], sample: bool) -> Option<f64> {
+    Some(variance(data, sample)?.to_f64().sqrt())
+}
+
+/// Quantile by linear interpolation between the closest ranks
+pub fn quantile(data: &[Fraction], q: Fraction) -> Option<Fraction> {
+    if data.is_empty() || !(0.0..=1.0).contains(&q.to_f64()) {
+        return None;
+    }
+    let data = sorted(data);
+    let position = q * Fraction::new((data.len() - 1) as f64);
+    let lower = position.to_f64().floor() as usize;
+    if lower + 1 >= data.len() {
+        return Some(data[data.len() - 1]);
+    }
+    let fraction = position - Fraction::new(lower as f64);
+    Some(data[lower] + fraction * (data[lower + 1] - data[lower]))
+}
+
+/// Interquartile range
+pub fn iqr(data: &[Fraction]) -> Option<Fraction> {
+    Some(quantile(data, Fraction::new(0.75))? - quantile(data, Fraction::new(0.25))?)
+}
+
+/// Skewness by moments of population
+pub fn skewness(data: &[Fraction]) -> Option<f64> {
+    let m2 = moment(data, 2)?.to_f64();
+    let m3 = moment(data, 3)?.to_f64();
+    (m2 != 0.0).then(|| m3 / m2.powf(1.5))
+}
+
+/// Excess kurtosis by moments of population
+pub fn kurtosis(data: &[Fraction]) -> Option<Fraction> {
+    let m2 = moment(data, 2)?;
+    let m4 = moment(data, 4)?;
+    (m2.numerator != 0).then(|| m4 / (m2 * m2) - Fraction::new(3.0))
+}
+
+/// Covariance, unbiased one of sample or that of population
+pub fn covariance(x: &[Fraction], y: &[Fraction], sample: bool) -> Option<Fraction> {
+    let n = x.len();
+    if n != y.len() || n < if sample { 2 } else { 1 } {
+        return None;
+    }
+    let (mean_x, mean_y) = (mean(x)?, mean(y)?);
+    let products: Vec<Fraction> = x
+        .iter()
+        .zip(y)
+        .map(|(a, b)| (*a - mean_x) * (*b - mean_y))
+        .collect();
+    let degrees = if sample { n - 1 } else { n };
+    Some(sum(&products) / Fraction::new(degrees as f64))
+}
+
+/// Pearson's correlation coefficient
+pub fn correlation(x: &[Fraction], y: &[Fraction]) -> Option<f64> {
+    let product = variance(x, false)?.to_f64() * variance(y, false)?.to_f64();
+    let covariance = covariance(x, y, false)?.to_f64();
+    (product != 0.0).then(|| covariance / product.sqrt())
+}
+
+/// Rule to choose bins of histogram
+pub enum Bins {
+    Count(usize),
+    Width(Fraction),
+    Sturges,
+    FreedmanDiaconis,
+}
+
+/// Edges of equal bins covering the data, the last bin includes its right edge
+pub fn bin_edges(data: &[Fraction], bins: Bins) -> Option<Vec<Fraction>> {
+    let data = sorted(data);
+    let (&low, &high) = (data.first()?, data.last()?);
+    let range = high - low;
+    let sturges = (data.len() as f64).log2().ceil() as usize + 1;
+    let count = match bins {
+        Bins::Count(count) => count,
+        Bins::Width(width) if width.to_f64() > 0.0 => {
+            (range / width).to_f64().ceil().max(1.0) as usize
+        }
+        Bins::Width(_) => return None,
+        Bins::Sturges => sturges,
+        Bins::FreedmanDiaconis => {
+            let width = 2.0 * iqr(&data)?.to_f64() / (data.len() as f64).cbrt();
+            if width > 0.0 {
+                (range.to_f64() / width).ceil().max(1.0) as usize
+            } else {
+                sturges
+            }
+        }
+    };
+    if count == 0 {
+        return None;
+    }
+
+    // Data of single value is put in the unit bin around it
+    if range.numerator == 0 {
+        let half = Fraction::new(0.5);
+        return Some(vec![low - half, high + half]);
+    }
+    let width = match bins {
+        Bins::Width(width) => width,
+        _ => range / Fraction::new(count as f64),
+    };
+    Some(
+        (0..=count)
+            .map(|i| low + width * Fraction::new(i as f64))
+            .collect(),
+    )
+}
+
+/// Count of the data in each bin between ascending edges,
+/// bins are half-open except the last closed one
+pub fn histogram(data: &[Fraction], edges: &[Fraction]) -> Vec<usize> {
+    let mut counts = vec![0; edges.len().saturating_sub(1)];
+    if counts.is_empty() {
+        return counts;
+    }
+    for x in data {
+        // Number of edges at or below the value
+        let position = edges.partition_point(|edge| compare(edge, x) != Ordering::Greater);
+        if position == 0 {
+            continue;
+        }
+        if position < edges.len() {
+            counts[position - 1] += 1;
+        } else if compare(x, &edges[edges.len() - 1]) == Ordering::Equal {
+            counts[position - 2] += 1;
+        }
+    }
+    counts
+}
@@ -0,0 +1,376 @@
+use crate::statistics;
+use crate::{Fraction, Type};
+use std::cmp::Ordering;
+
+/// Column of values of the same type
+#[derive(Clone, Debug)]
+pub enum Column {
+    Number(Vec<Fraction>),
+    String(Vec<String>),
+}
+
+impl Column {
+    fn len(&self) -> usize {
+        match self {
+            Column::Number(values) => values.len(),
+            Column::String(values) => values.len(),
+        }
+    }
+
+    /// Value of the row
+    pub fn get(&self, row: usize) -> Type {
+        match self {
+            Column::Number(values) => Type::Number(values[row]),
+            Column::String(values) => Type::String(values[row].clone()),
+        }
+    }
+
+    /// Column of the rows in the order
+    fn take(&self, rows: &[usize]) -> Column {
+        match self {
+            Column::Number(values) => Column::Number(rows.iter().map(|i| values[*i]).collect()),
+            Column::String(values) => {
+                Column::String(rows.iter().map(|i| values[*i].clone()).collect())
+            }
+        }
+    }
+
+    /// Order of values of two rows
+    fn compare(&self, a: usize, b: usize) -> Ordering {
+        match self {
+            Column::Number(values) => statistics::compare(&values[a], &values[b]),
+            Column::String(values) => values[a].cmp(&values[b]),
+        }
+    }
+
+    /// Column of numbers if every value is one, otherwise of strings
+    fn from_values(values: Vec<Type>) -> Column {
+        if values.iter().all(|x| matches!(x, Type::Number(_))) {
+            Column::Number(values.into_iter().map(|mut x| x.get_number()).collect())
+        } else {
+            Column::String(values.into_iter().map(|mut x| x.get_string()).collect())
+        }
+    }
+
+    /// Column parsed from text of cells, numeric if every cell is a number
+    fn parse(cells: Vec<String>) -> Column {
+        let numbers: Option<Vec<Fraction>> = cells
+            .iter()
+            .map(|cell| cell.trim().parse::<f64>().ok().map(Fraction::new))
+            .collect();
+        match numbers {
+            Some(numbers) if !cells.is_empty() => Column::Number(numbers),
+            _ => Column::String(cells),
+        }
+    }
+
+    /// Text of the cell in CSV
+    fn cell(&self, row: usize) -> String {
+        match self {
+            Column::Number(values) => {
+                let x = values[row];
+                if x.denominator == 1 {
+                    x.numerator.to_string()
+                } else {
+                    x.to_f64().to_string()
+                }
+            }
+            Column::String(values) => {
+                let value = &values[row];
+                if value.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", value.replace('"', "\"\""))
+                } else {
+                    value.clone()
+                }
+            }
+        }
+    }
+}
+
+/// Table of named columns
+#[derive(Clone, Debug)]
+pub struct Table {
+    names: Vec<String>,
+    columns: Vec<Column>,
+}
+
+/// Split CSV into records of fields, quoted fields may contain commas, quotes and newlines
+fn parse_records(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\n' | '\r' if !quoted => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("quotation is not closed".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    // Skip blank lines
+    records.retain(|record| !(record.len() == 1 && record[0].is_empty()));
+    Ok(records)
+}
+
+impl Table {
+    /// Constructor from names and columns, fails if they are mismatched
+    pub fn new(names: Vec<String>, columns: Vec<Vec<Type>>) -> Result<Table, String> {
+        if names.len() != columns.len() {
+            return Err("count of names and columns are different".to_string());
+        }
+        if columns
+            .windows(2)
+            .any(|pair| pair[0].len() != pair[1].len())
+        {
+            return Err("lengths of columns are different".to_string());
+        }
+        Ok(Table {
+            names,
+            columns: columns.into_iter().map(Column::from_values).collect(),
+        })
+    }
+
+    /// Parse CSV whose first record is the header
+    pub fn from_csv(text: &str) -> Result<Table, String> {
+        let mut records = parse_records(text)?.into_iter();
+        let names = records.next().ok_or("header is missing")?;
+        let mut cells: Vec<Vec<String>> = vec![vec![]; names.len()];
+        for (i, record) in records.enumerate() {
+            if record.len() != names.len() {
+                return Err(format!(
+                    "record {} has {} fields but header has {}",
+                    i + 1,
+                    record.len(),
+                    names.len()
+                ));
+            }
+            for (column, field) in cells.iter_mut().zip(record) {
+                column.push(field);
+            }
+        }
+        Ok(Table {
+            names: names.iter().map(|name| name.trim().to_string()).collect(),
+            columns: cells.into_iter().map(Column::parse).collect(),
+        })
+    }
+
+    /// Format as CSV
+    pub fn to_csv(&self) -> String {
+        let header = Column::String(self.names.clone());
+        let mut text = (0..self.names.len())
+            .map(|j| header.cell(j))
+            .collect::<Vec<String>>()
+            .join(",");
+        text.push('\n');
+        for i in 0..self.rows() {
+            let record: Vec<String> = self.columns.iter().map(|column| column.cell(i)).collect();
+            text += &record.join(",");
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn display(&self) -> String {
+        let mut text = format!("table:{{ {}", self.names.join(", "));
+        for i in 0..self.rows() {
+            let record: Vec<String> = self
+                .columns
+                .iter()
+                .map(|column| column.get(i).display())
+                .collect();
+            text += &format!("; {}", record.join(", "));
+        }
+        text + " }"
+    }
+
+    pub fn rows(&self) -> usize {
+        self.columns.first().map_or(0, Column::len)
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Column of the name
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        let index = self.names.iter().position(|x| x == name)?;
+        Some(&self.columns[index])
+    }
+
+    /// Rows as lists of values
+    pub fn to_list(&self) -> Vec<Type> {
+        (0..self.rows())
+            .map(|i| Type::List(self.columns.iter().map(|column| column.get(i)).collect()))
+            .collect()
+    }
+
+    /// Table of the columns in the order, fails if a name is unknown
+    pub fn select(&self, names: &[String]) -> Option<Table> {
+        let columns = names
+            .iter()
+            .map(|name| self.column(name).cloned())
+            .collect::<Option<Vec<Column>>>()?;
+        Some(Table {
+            names: names.to_vec(),
+            columns,
+        })
+    }
+
+    /// Table of the rows in the order
+    pub fn take(&self, rows: &[usize]) -> Table {
+        Table {
+            names: self.names.clone(),
+            columns: self
+                .columns
+                .iter()
+                .map(|column| column.take(rows))
+                .collect(),
+        }
+    }
+
+    /// Table stably sorted by the column
+    pub fn sort_by(&self, name: &str, descending: bool) -> Option<Table> {
+        let column = self.column(name)?;
+        let mut rows: Vec<usize> = (0..self.rows()).collect();
+        rows.sort_by(|a, b| {
+            let order = column.compare(*a, *b);
+            if descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+        Some(self.take(&rows))
+    }
+
+    /// Table of each group of the key sorted by it, whose numeric columns
+    /// are aggregated by the function, and count gives size of the group
+    pub fn group_by(&self, key: &str, function: &str) -> Option<Table> {
+        let aggregate: fn(&[Fraction]) -> Option<Fraction> = match function {
+            "count" => |x| Some(Fraction::new(x.len() as f64)),
+            "sum" => |x| Some(statistics::sum(x)),
+            "mean" => statistics::mean,
+            "median" => statistics::median,
+            "min" => |x| x.iter().copied().min_by(statistics::compare),
+            "max" => |x| x.iter().copied().max_by(statistics::compare),
+            "variance" => |x| statistics::variance(x, true),
+            "stdev" => |x| statistics::stdev(x, true).map(Fraction::new),
+            _ => return None,
+        };
+        let sorted = self.sort_by(key, false)?;
+        let key_index = self.names.iter().position(|x| x == key)?;
+        let key_column = &sorted.columns[key_index];
+
+        // Boundaries of runs of equal keys
+        let mut starts = vec![];
+        for i in 0..sorted.rows() {
+            if i == 0 || key_column.compare(i - 1, i) != Ordering::Equal {
+                starts.push(i);
+            }
+        }
+        let ranges: Vec<(usize, usize)> = starts
+            .iter()
+            .zip(starts.iter().skip(1).chain([&sorted.rows()]))
+            .map(|(a, b)| (*a, *b))
+            .collect();
+
+        let mut names = vec![key.to_string()];
+        let mut columns = vec![key_column.take(&starts)];
+        if function == "count" {
+            let counts = ranges.iter().map(|(a, b)| Fraction::new((b - a) as f64));
+            names.push("count".to_string());
+            columns.push(Column::Number(counts.collect()));
+            return Some(Table { names, columns });
+        }
+        for (name, column) in sorted.names.iter().zip(&sorted.columns) {
+            let values = match column {
+                Column::Number(values) if name != key => values,
+                _ => continue,
+            };
+            let aggregated = ranges
+                .iter()
+                .map(|(a, b)| aggregate(&values[*a..*b]))
+                .collect::<Option<Vec<Fraction>>>()?;
+            names.push(name.clone());
+            columns.push(Column::Number(aggregated));
+        }
+        Some(Table { names, columns })
+    }
+
+    /// Inner join on equal values of the key column, whose other columns of the right
+    /// table are suffixed if the names collide
+    pub fn join(&self, other: &Table, key: &str) -> Option<Table> {
+        let (left, right) = (self.column(key)?, other.column(key)?);
+        let mut pairs: Vec<(usize, usize)> = Vec::new();
+        for i in 0..self.rows() {
+            for j in 0..other.rows() {
+                let equal = match (left, right) {
+                    (Column::Number(a), Column::Number(b)) => {
+                        statistics::compare(&a[i], &b[j]) == Ordering::Equal
+                    }
+                    (Column::String(a), Column::String(b)) => a[i] == b[j],
+                    _ => left.get(i).display() == right.get(j).display(),
+                };
+                if equal {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        let (left_rows, right_rows): (Vec<usize>, Vec<usize>) = pairs.into_iter().unzip();
+
+        let mut table = self.take(&left_rows);
+        for (name, column) in other.names.iter().zip(&other.columns) {
+            if name == key {
+                continue;
+            }
+            let name = if table.names.contains(name) {
+                format!("{name}-right")
+            } else {
+                name.clone()
+            };
+            table.names.push(name);
+            table.columns.push(column.take(&right_rows));
+        }
+        Some(table)
+    }
+
+    /// Matrix of the numeric columns
+    pub fn to_matrix(&self) -> (Vec<Fraction>, (usize, usize)) {
+        let numeric: Vec<&Vec<Fraction>> = self
+            .columns
+            .iter()
+            .filter_map(|column| match column {
+                Column::Number(values) => Some(values),
+                Column::String(_) => None,
+            })
+            .collect();
+        let rows = self.rows();
+        let data = (0..rows)
+            .flat_map(|i| numeric.iter().map(move |column| column[i]))
+            .collect();
+        (data, (rows, numeric.len()))
+    }
+}